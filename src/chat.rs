@@ -0,0 +1,491 @@
+use crate::conventional;
+use crate::core::CommitInfo;
+use crate::release::{self, ReleaseNotes};
+use crate::utils;
+use serde_json::{Value, json};
+// Slack: 50 blocks per message, 150 characters in a header and 3000 in a
+// section's text.
+const SLACK_BLOCKS: usize = 50;
+const SLACK_HEADER: usize = 150;
+const SLACK_SECTION: usize = 3000;
+// Discord: 256 characters in a title or field name, 4096 in the description,
+// 25 fields of up to 1024 characters and 6000 characters per embed overall.
+const DISCORD_TITLE: usize = 256;
+const DISCORD_DESCRIPTION: usize = 4096;
+const DISCORD_FIELDS: usize = 25;
+const DISCORD_FIELD: usize = 1024;
+const DISCORD_TOTAL: usize = 6000;
+// Teams rejects webhook payloads larger than 28 KB.
+const TEAMS_BYTES: usize = 28 * 1024;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Platform {
+    Slack,
+    Discord,
+    Teams,
+}
+pub fn payload(
+    platform: Platform,
+    notes: &ReleaseNotes,
+    project: &str,
+    base_url: Option<&str>,
+    date_format: &str,
+) -> Value {
+    let title = format!("{} {}", project, notes.version);
+    let summary = format!(
+        "{} {} with {} commit(s) from {} contributor(s)",
+        if notes.tag.is_some() {
+            "Released"
+        } else {
+            "Unreleased as of"
+        },
+        notes.date.format(date_format),
+        notes.commit_count,
+        notes.contributor_count
+    );
+    match platform {
+        Platform::Slack => slack(notes, &title, &summary, base_url),
+        Platform::Discord => discord(notes, &title, &summary, base_url),
+        Platform::Teams => teams(notes, &title, &summary, base_url),
+    }
+}
+fn slack(notes: &ReleaseNotes, title: &str, summary: &str, base_url: Option<&str>) -> Value {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {"type": "plain_text", "text": clip(title, SLACK_HEADER), "emoji": true},
+        }),
+        json!({
+            "type": "context",
+            "elements": [{"type": "mrkdwn", "text": escape(summary)}],
+        }),
+    ];
+    let footer = notes.compare_url.as_ref().map(|url| {
+        json!({
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": format!("<{}|Full changelog>", url)},
+        })
+    });
+
+    let mut omitted = 0;
+    for (index, group) in notes.groups.iter().enumerate() {
+        // One block stays free for the footer and one for the final "and N
+        // more" when groups run out of room, or already have.
+        let reserved = usize::from(footer.is_some())
+            + usize::from(omitted > 0 || index + 1 < notes.groups.len());
+        if blocks.len() + reserved >= SLACK_BLOCKS {
+            omitted += group.commits.len();
+            continue;
+        }
+        let heading = format!("*{}*\n", escape(&group.name));
+        let lines: Vec<String> = group
+            .commits
+            .iter()
+            .map(|commit| {
+                let hash = match base_url {
+                    Some(base) => format!(
+                        "<{}|{}>",
+                        release::commit_link(base, &commit.hash),
+                        commit.short_hash
+                    ),
+                    None => format!("`{}`", commit.short_hash),
+                };
+                let breaking = if commit.breaking { "*BREAKING:* " } else { "" };
+                format!("• {}{} ({})", breaking, escape(&entry_text(commit)), hash)
+            })
+            .collect();
+        let text = fit(
+            &lines,
+            SLACK_SECTION - heading.chars().count(),
+            |line| line.chars().count(),
+            |count| format!("_and {} more_", count),
+        );
+        blocks.push(json!({
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": heading + &text},
+        }));
+    }
+    if omitted > 0 {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{"type": "mrkdwn", "text": format!("_and {} more_", omitted)}],
+        }));
+    }
+    blocks.extend(footer);
+
+    json!({
+        "text": format!("{}: {}", title, summary),
+        "blocks": blocks,
+    })
+}
+fn discord(notes: &ReleaseNotes, title: &str, summary: &str, base_url: Option<&str>) -> Value {
+    let escape = |text: &str| {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '[' | ']') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    };
+    let title = clip(title, DISCORD_TITLE);
+    let description = clip(summary, DISCORD_DESCRIPTION);
+    let more = |count: usize| format!("*and {} more*", count);
+    let length = |text: &str| text.chars().count();
+
+    // Room is kept for a final field listing what did not fit.
+    let closing = "More changes".len() + more(notes.commit_count).len();
+    let mut remaining = DISCORD_TOTAL - length(&title) - length(&description) - closing;
+    let mut fields = Vec::new();
+    let mut omitted = 0;
+    for group in &notes.groups {
+        let name = clip(&group.name, DISCORD_TITLE);
+        let room = remaining.saturating_sub(length(&name)).min(DISCORD_FIELD);
+        if fields.len() + 1 >= DISCORD_FIELDS || room < more(group.commits.len()).len() {
+            omitted += group.commits.len();
+            continue;
+        }
+        let lines: Vec<String> = group
+            .commits
+            .iter()
+            .map(|commit| {
+                let hash = match base_url {
+                    Some(base) => format!(
+                        "[`{}`]({})",
+                        commit.short_hash,
+                        release::commit_link(base, &commit.hash)
+                    ),
+                    None => format!("`{}`", commit.short_hash),
+                };
+                let breaking = if commit.breaking {
+                    "**BREAKING:** "
+                } else {
+                    ""
+                };
+                format!("• {}{} ({})", breaking, escape(&entry_text(commit)), hash)
+            })
+            .collect();
+        let value = fit(&lines, room, length, more);
+        remaining -= length(&name) + length(&value);
+        fields.push(json!({"name": name, "value": value, "inline": false}));
+    }
+    if omitted > 0 {
+        fields.push(json!({"name": "More changes", "value": more(omitted), "inline": false}));
+    }
+
+    let mut embed = json!({
+        "title": title,
+        "description": description,
+        "timestamp": notes.date.to_rfc3339(),
+        "fields": fields,
+    });
+    let link = notes.compare_url.clone().or_else(|| {
+        base_url
+            .zip(notes.tag.as_deref())
+            .map(|(base, tag)| release::tag_link(base, tag))
+    });
+    if let Some(link) = link {
+        embed["url"] = json!(link);
+    }
+    json!({"embeds": [embed]})
+}
+fn teams(notes: &ReleaseNotes, title: &str, summary: &str, base_url: Option<&str>) -> Value {
+    let mut card = json!({
+        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+        "type": "AdaptiveCard",
+        "version": "1.4",
+        "body": [
+            {"type": "TextBlock", "text": title, "size": "Large", "weight": "Bolder", "wrap": true},
+            {"type": "TextBlock", "text": summary, "isSubtle": true, "spacing": "None", "wrap": true},
+        ],
+    });
+    if let Some(url) = &notes.compare_url {
+        card["actions"] =
+            json!([{"type": "Action.OpenUrl", "title": "Full changelog", "url": url}]);
+    }
+    let wrap = |card: Value| {
+        json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "contentUrl": null,
+                "content": card,
+            }],
+        })
+    };
+    // Sizes are measured as serialized JSON, since that is what the limit
+    // applies to.
+    let size = |value: &Value| value.to_string().len();
+    let length = |text: &str| Value::from(text).to_string().len() - 2;
+    let more = |count: usize| format!("_and {} more_", count);
+    let block = |name: &str, text: &str| {
+        [
+            json!({"type": "TextBlock", "text": name, "weight": "Bolder", "separator": true, "wrap": true}),
+            json!({"type": "TextBlock", "text": text, "wrap": true}),
+        ]
+    };
+
+    let closing = size(&block("", &more(notes.commit_count))[1]) + 1;
+    let mut remaining = TEAMS_BYTES.saturating_sub(size(&wrap(card.clone())) + closing);
+    let mut body = Vec::new();
+    let mut omitted = 0;
+    for group in &notes.groups {
+        let overhead = block(&group.name, "")
+            .iter()
+            .map(|b| size(b) + 1)
+            .sum::<usize>();
+        let room = remaining.saturating_sub(overhead);
+        if room < length(&more(group.commits.len())) {
+            omitted += group.commits.len();
+            continue;
+        }
+        let lines: Vec<String> = group
+            .commits
+            .iter()
+            .map(|commit| {
+                let hash = match base_url {
+                    Some(base) => format!(
+                        "[{}]({})",
+                        commit.short_hash,
+                        release::commit_link(base, &commit.hash)
+                    ),
+                    None => commit.short_hash.clone(),
+                };
+                let breaking = if commit.breaking {
+                    "**BREAKING:** "
+                } else {
+                    ""
+                };
+                format!("- {}{} ({})", breaking, entry_text(commit), hash)
+            })
+            .collect();
+        // Line breaks are escaped as two bytes, one more than `fit` counts.
+        let text = fit(&lines, room, |line| length(line) + 1, more);
+        remaining -= overhead + length(&text);
+        body.extend(block(&group.name, &text));
+    }
+    if omitted > 0 {
+        body.push(json!({"type": "TextBlock", "text": more(omitted), "wrap": true}));
+    }
+    if let Some(blocks) = card["body"].as_array_mut() {
+        blocks.extend(body);
+    }
+    wrap(card)
+}
+fn entry_text(commit: &CommitInfo) -> String {
+    let text = utils::capitalize(conventional::description(&commit.summary));
+    match &commit.scope {
+        Some(scope) => format!("{}: {}", scope, text),
+        None => text,
+    }
+}
+// Joins as many lines as fit in `limit`, as measured by `length`, and ends
+// with an "and N more" line for the rest.
+fn fit(
+    lines: &[String],
+    limit: usize,
+    length: impl Fn(&str) -> usize,
+    more: impl Fn(usize) -> String,
+) -> String {
+    let total: usize = lines.iter().map(|line| length(line) + 1).sum();
+    if total.saturating_sub(1) <= limit {
+        return lines.join("\n");
+    }
+    let mut kept = Vec::new();
+    let mut used = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let tail = length(&more(lines.len() - index - 1)) + 1;
+        if used + length(line) + 1 + tail > limit {
+            break;
+        }
+        used += length(line) + 1;
+        kept.push(line.clone());
+    }
+    kept.push(more(lines.len() - kept.len()));
+    kept.join("\n")
+}
+fn clip(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut clipped: String = text.chars().take(limit - 1).collect();
+    clipped.push('…');
+    clipped
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CommitGroup;
+    use regex::Regex;
+    use serde_json::json;
+    fn commit(index: usize, summary: &str) -> CommitInfo {
+        let hash = format!("{:040x}", index);
+        serde_json::from_value(json!({
+            "hash": hash,
+            "short_hash": &hash[32..],
+            "author": {"name": "Alice", "email": "alice@example.com", "commits_count": 1},
+            "date": "2024-02-15T10:00:00+00:00",
+            "message": summary,
+            "summary": summary,
+            "body": null,
+            "files_changed": [],
+            "insertions": 0,
+            "deletions": 0,
+            "is_merge": false,
+            "tags": [],
+            "branches": [],
+            "commit_type": "feat",
+            "scope": "api"
+        }))
+        .unwrap()
+    }
+    // `groups` groups of `per_group` commits whose summaries are `width`
+    // characters long.
+    fn notes(groups: usize, per_group: usize, width: usize) -> ReleaseNotes {
+        let mut index = 0;
+        let groups: Vec<CommitGroup> = (0..groups)
+            .map(|group| CommitGroup {
+                name: format!("Group {}", group),
+                commits: (0..per_group)
+                    .map(|_| {
+                        index += 1;
+                        commit(index, &format!("feat: {}", "é".repeat(width)))
+                    })
+                    .collect(),
+                description: None,
+            })
+            .collect();
+        ReleaseNotes {
+            version: "v2.0.0".to_string(),
+            tag: Some("v2.0.0".to_string()),
+            previous_tag: Some("v1.0.0".to_string()),
+            date: "2024-02-15T10:00:00+00:00".parse().unwrap(),
+            commit_count: index,
+            contributor_count: 1,
+            highlights: Vec::new(),
+            breaking: Vec::new(),
+            groups,
+            new_contributors: Vec::new(),
+            compare_url: Some("https://github.com/acme/widget/compare/v1.0.0...v2.0.0".to_string()),
+            tag_details: None,
+        }
+    }
+    fn render(platform: Platform, notes: &ReleaseNotes) -> Value {
+        payload(
+            platform,
+            notes,
+            "widget",
+            Some("https://github.com/acme/widget"),
+            "%Y-%m-%d",
+        )
+    }
+    // Every commit is either listed on its own line or counted in an "and N
+    // more" line.
+    fn accounted(texts: &[String], bullet: &str) -> usize {
+        let more = Regex::new(r"and (\d+) more").unwrap();
+        texts
+            .iter()
+            .flat_map(|text| text.lines())
+            .map(|line| match more.captures(line) {
+                Some(captures) => captures[1].parse().unwrap(),
+                None => usize::from(line.starts_with(bullet)),
+            })
+            .sum()
+    }
+    fn strings<'a>(values: impl Iterator<Item = &'a Value>) -> Vec<String> {
+        values
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect()
+    }
+    #[test]
+    fn small_releases_are_not_truncated() {
+        let notes = notes(2, 3, 20);
+        let payload = render(Platform::Slack, &notes).to_string();
+        assert!(!payload.contains("more_"));
+        let payload = render(Platform::Discord, &notes).to_string();
+        assert!(!payload.contains("more*"));
+    }
+    #[test]
+    fn slack_respects_block_and_text_limits() {
+        for notes in [notes(80, 2, 20), notes(3, 400, 60)] {
+            let payload = render(Platform::Slack, &notes);
+            let blocks = payload["blocks"].as_array().unwrap();
+            assert!(blocks.len() <= SLACK_BLOCKS, "{} blocks", blocks.len());
+            let sections = strings(
+                blocks
+                    .iter()
+                    .filter(|block| block["type"] == "section")
+                    .map(|block| &block["text"]["text"]),
+            );
+            for text in &sections {
+                assert!(text.chars().count() <= SLACK_SECTION);
+            }
+            let contexts = strings(
+                blocks
+                    .iter()
+                    .skip(2)
+                    .filter(|block| block["type"] == "context")
+                    .map(|block| &block["elements"][0]["text"]),
+            );
+            let texts: Vec<String> = sections.into_iter().chain(contexts).collect();
+            assert!(texts.iter().any(|text| text.contains("more_")));
+            assert_eq!(accounted(&texts, "• "), notes.commit_count);
+            assert!(
+                blocks.last().unwrap()["text"]["text"]
+                    .as_str()
+                    .unwrap()
+                    .contains("Full changelog")
+            );
+        }
+    }
+    #[test]
+    fn discord_respects_embed_limits() {
+        for notes in [notes(40, 2, 20), notes(3, 400, 60)] {
+            let payload = render(Platform::Discord, &notes);
+            let embed = &payload["embeds"][0];
+            let fields = embed["fields"].as_array().unwrap();
+            assert!(fields.len() <= DISCORD_FIELDS);
+            let mut total = embed["title"].as_str().unwrap().chars().count()
+                + embed["description"].as_str().unwrap().chars().count();
+            for field in fields {
+                let value = field["value"].as_str().unwrap();
+                assert!(value.chars().count() <= DISCORD_FIELD);
+                total += field["name"].as_str().unwrap().chars().count() + value.chars().count();
+            }
+            assert!(total <= DISCORD_TOTAL, "{} characters", total);
+            let values = strings(fields.iter().map(|field| &field["value"]));
+            assert!(values.iter().any(|value| value.contains("more*")));
+            assert_eq!(accounted(&values, "• "), notes.commit_count);
+        }
+    }
+    #[test]
+    fn teams_respects_the_payload_size() {
+        for notes in [notes(200, 2, 20), notes(3, 400, 60)] {
+            let payload = render(Platform::Teams, &notes);
+            assert!(payload.to_string().len() <= TEAMS_BYTES);
+            let body = payload["attachments"][0]["content"]["body"]
+                .as_array()
+                .unwrap();
+            let texts = strings(body.iter().skip(2).map(|block| &block["text"]));
+            assert!(texts.iter().any(|text| text.contains("more_")));
+            assert_eq!(accounted(&texts, "- "), notes.commit_count);
+        }
+    }
+    #[test]
+    fn fit_and_clip() {
+        let lines: Vec<String> = (0..10).map(|i| format!("line {}", i)).collect();
+        let more = |count: usize| format!("and {} more", count);
+        let length = |text: &str| text.chars().count();
+        assert_eq!(fit(&lines, 1000, length, more), lines.join("\n"));
+        let text = fit(&lines, 30, length, more);
+        assert!(text.chars().count() <= 30);
+        assert_eq!(text, "line 0\nline 1\nand 8 more");
+        assert_eq!(clip("ééééé", 5), "ééééé");
+        assert_eq!(clip("éééééé", 5), "éééé…");
+    }
+}
@@ -1,9 +1,53 @@
 use clap::{Parser, Subcommand, ValueEnum};
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Md,
     Json,
     Text,
+    Csv,
+    Keepachangelog,
+    Html,
+    Atom,
+    Rss,
+    Debian,
+    Rpm,
+    Ndjson,
+    Adoc,
+    Rst,
+    News,
+    Slack,
+    Discord,
+    Teams,
+    Eml,
+    Mbox,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum StatsFormat {
+    Md,
+    Json,
+    Text,
+    Csv,
+    Html,
+    Ndjson,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum LintFormat {
+    Md,
+    Json,
+    Text,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum WhichReleaseFormat {
+    Md,
+    Json,
+    Text,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum TagsFormat {
+    Md,
+    Json,
+    Text,
+    Csv,
 }
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum SortOrder {
@@ -11,6 +55,17 @@ pub enum SortOrder {
     Reverse,
     Author,
 }
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
 #[derive(Debug, Parser)]
 #[command(
     name = "rcgen",
@@ -38,6 +93,8 @@ pub enum Commands {
         #[arg(long)]
         grep: Option<String>,
         #[arg(long)]
+        filter: Option<String>,
+        #[arg(long)]
         since: Option<String>,
         #[arg(long)]
         until: Option<String>,
@@ -55,14 +112,20 @@ pub enum Commands {
         release: bool,
         #[arg(short = 'd', long, default_value_t = false)]
         diff_stats: bool,
+        #[arg(long)]
+        formatter: Option<String>,
+        #[arg(long, value_enum, default_value_t = Theme::Light)]
+        theme: Theme,
     },
     Stats {
         #[arg(short, long, default_value = ".")]
         path: String,
         #[arg(long, default_value_t = false)]
         detailed: bool,
-        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
-        format: OutputFormat,
+        #[arg(short, long, value_enum, default_value_t = StatsFormat::Text)]
+        format: StatsFormat,
+        #[arg(long, value_enum, default_value_t = Theme::Light)]
+        theme: Theme,
     },
     Diff {
         #[arg(short, long, default_value = ".")]
@@ -78,6 +141,48 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         force: bool,
     },
+    Lint {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        range: Option<String>,
+        #[arg(long, conflicts_with = "range")]
+        message_file: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = LintFormat::Text)]
+        format: LintFormat,
+    },
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+    Release {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        #[arg(long, value_enum, conflicts_with = "version")]
+        bump: Option<Bump>,
+        #[arg(long)]
+        version: Option<String>,
+    },
+    WhichRelease {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        query: String,
+        #[arg(short, long, value_enum, default_value_t = WhichReleaseFormat::Text)]
+        format: WhichReleaseFormat,
+    },
+    Tags {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        #[arg(short, long, value_enum, default_value_t = TagsFormat::Text)]
+        format: TagsFormat,
+    },
+    Export {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        #[arg(long)]
+        sqlite: String,
+    },
     Preview {
         #[arg(short, long, default_value = ".")]
         path: String,
@@ -85,3 +190,26 @@ pub enum Commands {
         limit: usize,
     },
 }
+#[derive(Debug, Subcommand)]
+pub enum HooksAction {
+    Install {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        #[arg(long, default_value_t = false)]
+        post_commit: bool,
+        #[arg(long, default_value_t = false)]
+        on_tag: bool,
+    },
+    Uninstall {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+    },
+    #[command(hide = true)]
+    Run {
+        #[arg(short, long, default_value = ".")]
+        path: String,
+        hook: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
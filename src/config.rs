@@ -1,7 +1,14 @@
 use crate::error::{RcgenError, Result};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::sync::LazyLock;
+// Group patterns anchored on a Conventional Commits type, e.g. `^feat:` or
+// `^fix\(`.
+static GROUP_TYPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\^([A-Za-z][A-Za-z0-9-]*)(?:[:(!]|\\\()").unwrap());
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub repository: RepositoryConfig,
@@ -9,12 +16,51 @@ pub struct Config {
     pub filters: FilterConfig,
     pub grouping: GroupingConfig,
     pub templates: TemplateConfig,
+    #[serde(default)]
+    pub plugins: PluginConfig,
+    #[serde(default)]
+    pub notes: NotesConfig,
+    #[serde(default)]
+    pub trailers: TrailerConfig,
+    #[serde(default)]
+    pub conventional: ConventionalConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub release: ReleaseConfig,
+    #[serde(default)]
+    pub keepachangelog: KeepAChangelogConfig,
+    #[serde(default)]
+    pub packaging: PackagingConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepositoryConfig {
     pub url: Option<String>,
     pub default_branch: String,
     pub tag_pattern: Option<String>,
+    #[serde(default)]
+    pub tag_prefix: Option<String>,
+    #[serde(default)]
+    pub prerelease: PrereleaseMode,
+    #[serde(default)]
+    pub non_semver: NonSemverMode,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrereleaseMode {
+    Collapse,
+    #[default]
+    Separate,
+    Skip,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NonSemverMode {
+    #[default]
+    Skip,
+    Date,
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OutputConfig {
@@ -23,12 +69,74 @@ pub struct OutputConfig {
     pub include_diff_stats: bool,
     pub exclude_merges: bool,
     pub max_commits: usize,
+    #[serde(default = "default_changelog_path")]
+    pub changelog_path: String,
+    #[serde(default = "default_csv_columns")]
+    pub csv_columns: Vec<CsvColumn>,
+    #[serde(default = "default_wrap_width")]
+    pub wrap_width: usize,
+}
+fn default_changelog_path() -> String {
+    "CHANGELOG.md".to_string()
+}
+fn default_csv_columns() -> Vec<CsvColumn> {
+    CsvColumn::ALL.to_vec()
+}
+pub fn default_wrap_width() -> usize {
+    72
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvColumn {
+    Hash,
+    Date,
+    Author,
+    Email,
+    Type,
+    Scope,
+    Summary,
+    Insertions,
+    Deletions,
+    Files,
+    Tags,
+}
+impl CsvColumn {
+    pub const ALL: [CsvColumn; 11] = [
+        CsvColumn::Hash,
+        CsvColumn::Date,
+        CsvColumn::Author,
+        CsvColumn::Email,
+        CsvColumn::Type,
+        CsvColumn::Scope,
+        CsvColumn::Summary,
+        CsvColumn::Insertions,
+        CsvColumn::Deletions,
+        CsvColumn::Files,
+        CsvColumn::Tags,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            CsvColumn::Hash => "hash",
+            CsvColumn::Date => "date",
+            CsvColumn::Author => "author",
+            CsvColumn::Email => "email",
+            CsvColumn::Type => "type",
+            CsvColumn::Scope => "scope",
+            CsvColumn::Summary => "summary",
+            CsvColumn::Insertions => "insertions",
+            CsvColumn::Deletions => "deletions",
+            CsvColumn::Files => "files",
+            CsvColumn::Tags => "tags",
+        }
+    }
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FilterConfig {
     pub exclude_authors: Vec<String>,
     pub exclude_patterns: Vec<String>,
     pub include_patterns: Vec<String>,
+    #[serde(default)]
+    pub expression: Option<String>,
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GroupingConfig {
@@ -40,6 +148,17 @@ pub struct CommitGroup {
     pub name: String,
     pub patterns: Vec<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+impl CommitGroup {
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.patterns
+            .iter()
+            .filter_map(|p| GROUP_TYPE.captures(p))
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+    }
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TemplateConfig {
@@ -47,6 +166,261 @@ pub struct TemplateConfig {
     pub footer: Option<String>,
     pub commit_format: String,
     pub date_format: String,
+    pub feed_title: Option<String>,
+    pub feed_author: Option<String>,
+    pub feed_link: Option<String>,
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct KeepAChangelogConfig {
+    pub rules: Vec<CategoryRule>,
+    pub fallback: Option<ChangeCategory>,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeCategory {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+impl ChangeCategory {
+    pub const ALL: [ChangeCategory; 6] = [
+        ChangeCategory::Added,
+        ChangeCategory::Changed,
+        ChangeCategory::Deprecated,
+        ChangeCategory::Removed,
+        ChangeCategory::Fixed,
+        ChangeCategory::Security,
+    ];
+    pub fn title(self) -> &'static str {
+        match self {
+            ChangeCategory::Added => "Added",
+            ChangeCategory::Changed => "Changed",
+            ChangeCategory::Deprecated => "Deprecated",
+            ChangeCategory::Removed => "Removed",
+            ChangeCategory::Fixed => "Fixed",
+            ChangeCategory::Security => "Security",
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryRule {
+    pub category: ChangeCategory,
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub pattern: Option<String>,
+}
+impl CategoryRule {
+    fn new(
+        category: ChangeCategory,
+        types: &[&str],
+        scopes: &[&str],
+        pattern: Option<&str>,
+    ) -> Self {
+        Self {
+            category,
+            types: types.iter().map(|t| t.to_string()).collect(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            pattern: pattern.map(str::to_string),
+        }
+    }
+}
+impl Default for KeepAChangelogConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                CategoryRule::new(ChangeCategory::Security, &[], &["security"], None),
+                CategoryRule::new(
+                    ChangeCategory::Security,
+                    &[],
+                    &[],
+                    Some(r"(?i)\b(?:security|vulnerab\w*|cve-\d+)"),
+                ),
+                CategoryRule::new(
+                    ChangeCategory::Deprecated,
+                    &[],
+                    &[],
+                    Some(r"(?i)\bdeprecat"),
+                ),
+                CategoryRule::new(
+                    ChangeCategory::Removed,
+                    &[],
+                    &[],
+                    Some(r"(?i)^[^:]*:\s*(?:remove|drop|delete)\w*\b"),
+                ),
+                CategoryRule::new(ChangeCategory::Added, &["feat"], &[], None),
+                CategoryRule::new(ChangeCategory::Fixed, &["fix"], &[], None),
+                CategoryRule::new(ChangeCategory::Changed, &["perf", "refactor"], &[], None),
+            ],
+            fallback: None,
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReleaseConfig {
+    pub files: Vec<VersionFile>,
+    pub commit_message: String,
+}
+impl Default for ReleaseConfig {
+    fn default() -> Self {
+        Self {
+            files: vec![],
+            commit_message: "chore(release): {tag}".to_string(),
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PackagingConfig {
+    pub package: Option<String>,
+    pub maintainer: Option<String>,
+    pub distribution: String,
+    pub urgency: String,
+    pub revision: String,
+}
+impl Default for PackagingConfig {
+    fn default() -> Self {
+        Self {
+            package: None,
+            maintainer: None,
+            distribution: "unstable".to_string(),
+            urgency: "medium".to_string(),
+            revision: "1".to_string(),
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct EmailConfig {
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+}
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            from: None,
+            to: vec![],
+            cc: vec![],
+            subject: "[ANNOUNCE] {project} {version} released".to_string(),
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionFile {
+    pub path: String,
+    pub pattern: Option<String>,
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LintConfig {
+    pub max_summary_length: usize,
+    pub body_line_length: usize,
+    pub imperative_mood: bool,
+    pub required_trailers: Vec<String>,
+}
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_summary_length: 72,
+            body_line_length: 72,
+            imperative_mood: true,
+            required_trailers: vec![],
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConventionalConfig {
+    pub heuristics: Heuristics,
+    pub aliases: BTreeMap<String, String>,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Heuristics {
+    Off,
+    #[default]
+    Keywords,
+    Strict,
+}
+impl Default for ConventionalConfig {
+    fn default() -> Self {
+        Self {
+            heuristics: Heuristics::default(),
+            aliases: BTreeMap::from([
+                ("bugfix".to_string(), "fix".to_string()),
+                ("feature".to_string(), "feat".to_string()),
+            ]),
+        }
+    }
+}
+impl ConventionalConfig {
+    pub fn normalize_type(&self, commit_type: &str) -> String {
+        let commit_type = commit_type.to_lowercase();
+        self.aliases
+            .get(&commit_type)
+            .map(|t| t.to_lowercase())
+            .unwrap_or(commit_type)
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TrailerConfig {
+    pub skip: String,
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    pub scope: String,
+    pub entry: String,
+}
+impl Default for TrailerConfig {
+    fn default() -> Self {
+        Self {
+            skip: "Changelog".to_string(),
+            commit_type: "Changelog-Type".to_string(),
+            scope: "Changelog-Scope".to_string(),
+            entry: "Changelog-Entry".to_string(),
+        }
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NotesConfig {
+    #[serde(rename = "ref")]
+    pub notes_ref: Option<String>,
+    #[serde(default)]
+    pub mode: NotesMode,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotesMode {
+    #[default]
+    Replace,
+    Append,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PluginConfig {
+    #[serde(default)]
+    pub filters: Vec<PluginCommand>,
+    #[serde(default)]
+    pub formatters: Vec<PluginCommand>,
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginCommand {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_plugin_timeout")]
+    pub timeout_secs: u64,
+}
+fn default_plugin_timeout() -> u64 {
+    10
 }
 impl Default for Config {
     fn default() -> Self {
@@ -54,7 +428,10 @@ impl Default for Config {
             repository: RepositoryConfig {
                 url: None,
                 default_branch: "main".to_string(),
-                tag_pattern: Some("v[0-9]*".to_string()),
+                tag_pattern: None,
+                tag_prefix: None,
+                prerelease: PrereleaseMode::default(),
+                non_semver: NonSemverMode::default(),
             },
             output: OutputConfig {
                 default_format: "md".to_string(),
@@ -62,6 +439,9 @@ impl Default for Config {
                 include_diff_stats: true,
                 exclude_merges: true,
                 max_commits: 100,
+                changelog_path: default_changelog_path(),
+                csv_columns: default_csv_columns(),
+                wrap_width: default_wrap_width(),
             },
             filters: FilterConfig {
                 exclude_authors: vec![],
@@ -70,6 +450,7 @@ impl Default for Config {
                     "^Revert".to_string(),
                 ],
                 include_patterns: vec![],
+                expression: None,
             },
             grouping: GroupingConfig {
                 enabled: true,
@@ -83,6 +464,7 @@ impl Default for Config {
                             "new\\s".to_string(),
                         ],
                         description: Some("New features and enhancements".to_string()),
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Bug Fixes".to_string(),
@@ -93,6 +475,7 @@ impl Default for Config {
                             "bug(?:s)?\\s".to_string(),
                         ],
                         description: Some("Bug fixes and patches".to_string()),
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Documentation".to_string(),
@@ -102,6 +485,7 @@ impl Default for Config {
                             "readme".to_string(),
                         ],
                         description: None,
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Refactoring".to_string(),
@@ -111,6 +495,7 @@ impl Default for Config {
                             "cleanup".to_string(),
                         ],
                         description: None,
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Performance".to_string(),
@@ -120,6 +505,7 @@ impl Default for Config {
                             "optimize".to_string(),
                         ],
                         description: None,
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Tests".to_string(),
@@ -129,6 +515,7 @@ impl Default for Config {
                             "test(?:s|ed|ing)?\\s".to_string(),
                         ],
                         description: None,
+                        scopes: vec![],
                     },
                     CommitGroup {
                         name: "Chores".to_string(),
@@ -138,6 +525,7 @@ impl Default for Config {
                             "^build:".to_string(),
                         ],
                         description: Some("Maintenance tasks".to_string()),
+                        scopes: vec![],
                     },
                 ],
             },
@@ -146,7 +534,19 @@ impl Default for Config {
                 footer: Some("\n---\nGenerated by [rcgen](https://github.com/yourusername/rcgen)".to_string()),
                 commit_format: "- {message} ({hash} by {author})".to_string(),
                 date_format: "%Y-%m-%d".to_string(),
+                feed_title: None,
+                feed_author: None,
+                feed_link: None,
             },
+            plugins: PluginConfig::default(),
+            notes: NotesConfig::default(),
+            trailers: TrailerConfig::default(),
+            conventional: ConventionalConfig::default(),
+            lint: LintConfig::default(),
+            release: ReleaseConfig::default(),
+            keepachangelog: KeepAChangelogConfig::default(),
+            packaging: PackagingConfig::default(),
+            email: EmailConfig::default(),
         }
     }
 }
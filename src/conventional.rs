@@ -0,0 +1,285 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+static STRICT_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z][A-Za-z0-9-]*)(?:\(([^()\s][^()]*)\))?(!)?: (\S.*)$").unwrap()
+});
+static LENIENT_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\w+)(?:\(([^)]+)\))?(!)?:").unwrap());
+static FOOTER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(BREAKING CHANGE|BREAKING-CHANGE|[A-Za-z0-9][A-Za-z0-9-]*)(: | #)(.*)$").unwrap()
+});
+static KEYWORDS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (r"\b(?:fix(?:es|ed|ing)?|bugs?|bugfix)\b", "fix"),
+        (r"\b(?:feat|features?|add(?:s|ed|ing)?|new)\b", "feat"),
+        (r"\b(?:docs?|documentation|readme)\b", "docs"),
+        (r"\b(?:tests?|testing)\b", "test"),
+        (r"\b(?:refactor(?:s|ed|ing)?)\b", "refactor"),
+        (
+            r"\b(?:perf|performance|optimi[sz](?:e|es|ed|ing))\b",
+            "perf",
+        ),
+        (r"\b(?:chore|ci|build)\b", "chore"),
+    ]
+    .into_iter()
+    .map(|(pattern, commit_type)| (Regex::new(pattern).unwrap(), commit_type))
+    .collect()
+});
+// The types of the Conventional Commits specification and the Angular
+// convention it is based on.
+pub const STANDARD_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Footer {
+    pub token: String,
+    pub value: String,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConventionalCommit {
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+    pub body: Option<String>,
+    pub footers: Vec<Footer>,
+}
+impl ConventionalCommit {
+    pub fn is_breaking_footer(token: &str) -> bool {
+        token == "BREAKING CHANGE" || token == "BREAKING-CHANGE"
+    }
+}
+pub fn parse(message: &str) -> Result<ConventionalCommit, String> {
+    let message = message.trim_end();
+    let mut lines = message.lines();
+    let header = lines.next().unwrap_or("").trim_end();
+    let caps = STRICT_HEADER.captures(header).ok_or_else(|| {
+        "header must match `<type>[optional scope][!]: <description>`".to_string()
+    })?;
+    let rest: Vec<&str> = lines.collect();
+    if let Some(first) = rest.first()
+        && !first.trim().is_empty()
+    {
+        return Err("body must be separated from the header by a blank line".to_string());
+    }
+    let paragraphs = split_paragraphs(&rest);
+    let (body_paragraphs, footers) = match paragraphs.last() {
+        Some(last) if FOOTER.is_match(last[0]) => {
+            let footers = parse_footers(last);
+            (&paragraphs[..paragraphs.len() - 1], footers)
+        }
+        _ => (&paragraphs[..], Vec::new()),
+    };
+    let body = if body_paragraphs.is_empty() {
+        None
+    } else {
+        Some(
+            body_paragraphs
+                .iter()
+                .map(|p| p.join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        )
+    };
+    let breaking = caps.get(3).is_some()
+        || footers
+            .iter()
+            .any(|f| ConventionalCommit::is_breaking_footer(&f.token));
+    Ok(ConventionalCommit {
+        commit_type: caps[1].to_string(),
+        scope: caps.get(2).map(|m| m.as_str().to_string()),
+        breaking,
+        description: caps[4].to_string(),
+        body,
+        footers,
+    })
+}
+// Only prefixes accepted by `is_known` count as types, so that summaries such
+// as "WIP: ..." or "Note: ..." are not taken for headers.
+pub fn parse_lenient(
+    message: &str,
+    is_known: impl Fn(&str) -> bool,
+) -> Option<(String, Option<String>, bool)> {
+    let header = message.lines().next().unwrap_or("").trim();
+    let caps = LENIENT_HEADER.captures(header)?;
+    if !is_known(&caps[1]) {
+        return None;
+    }
+    let breaking = caps.get(3).is_some()
+        || message.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+    Some((
+        caps[1].to_string(),
+        caps.get(2).map(|m| m.as_str().to_string()),
+        breaking,
+    ))
+}
+pub fn description(summary: &str) -> &str {
+    match LENIENT_HEADER.find(summary) {
+        Some(header) => summary[header.end()..].trim(),
+        None => summary.trim(),
+    }
+}
+pub fn guess_type(summary: &str) -> Option<String> {
+    let summary = summary.to_lowercase();
+    KEYWORDS
+        .iter()
+        .find(|(regex, _)| regex.is_match(&summary))
+        .map(|(_, commit_type)| commit_type.to_string())
+}
+fn split_paragraphs<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut paragraphs = Vec::new();
+    let mut current = Vec::new();
+    for line in lines {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(*line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+    paragraphs
+}
+fn parse_footers(lines: &[&str]) -> Vec<Footer> {
+    let mut footers: Vec<Footer> = Vec::new();
+    for line in lines {
+        if let Some(caps) = FOOTER.captures(line) {
+            let value = caps[3].trim();
+            footers.push(Footer {
+                token: caps[1].to_string(),
+                value: if &caps[2] == " #" {
+                    format!("#{}", value)
+                } else {
+                    value.to_string()
+                },
+            });
+        } else if let Some(last) = footers.last_mut() {
+            last.value.push('\n');
+            last.value.push_str(line.trim_end());
+        }
+    }
+    footers
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn parses_type_scope_and_description() {
+        let commit = parse("feat(api): add users endpoint").unwrap();
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert_eq!(commit.description, "add users endpoint");
+        assert!(!commit.breaking);
+        assert_eq!(commit.body, None);
+        assert!(commit.footers.is_empty());
+    }
+    #[test]
+    fn bang_marks_a_breaking_change() {
+        assert!(parse("feat!: drop the v1 API").unwrap().breaking);
+        assert!(parse("refactor(db)!: new storage engine").unwrap().breaking);
+    }
+    #[test]
+    fn breaking_change_footer_marks_a_breaking_change() {
+        let commit = parse(
+            "fix: handle empty payload\n\nRequests without a body used to panic.\n\nBREAKING CHANGE: empty bodies now return 400\nRefs: #42",
+        )
+        .unwrap();
+        assert!(commit.breaking);
+        assert_eq!(
+            commit.body.as_deref(),
+            Some("Requests without a body used to panic.")
+        );
+        assert_eq!(
+            commit.footers,
+            vec![
+                Footer {
+                    token: "BREAKING CHANGE".to_string(),
+                    value: "empty bodies now return 400".to_string(),
+                },
+                Footer {
+                    token: "Refs".to_string(),
+                    value: "#42".to_string(),
+                },
+            ]
+        );
+        assert!(parse("fix: x\n\nBREAKING-CHANGE: y").unwrap().breaking);
+    }
+    #[test]
+    fn footers_support_the_hash_separator_and_continuation_lines() {
+        let commit = parse("fix: x\n\nCloses #12\nReviewed-by: Alice\n  and Bob").unwrap();
+        assert_eq!(commit.footers[0].value, "#12");
+        assert_eq!(commit.footers[1].value, "Alice\n  and Bob");
+        assert!(!commit.breaking);
+    }
+    #[test]
+    fn a_body_alone_is_not_mistaken_for_footers() {
+        let commit = parse("docs: explain setup\n\nSee the wiki for details.").unwrap();
+        assert_eq!(commit.body.as_deref(), Some("See the wiki for details."));
+        assert!(commit.footers.is_empty());
+    }
+    #[test]
+    fn rejects_malformed_headers() {
+        for message in [
+            "add users endpoint",
+            "feat:add users endpoint",
+            "feat : add users endpoint",
+            "feat(): add users endpoint",
+            "feat(api: add users endpoint",
+            "1feat: add users endpoint",
+            "feat: ",
+        ] {
+            assert!(parse(message).is_err(), "accepted `{}`", message);
+        }
+        assert!(parse("feat: x\nno blank line").is_err());
+    }
+    #[test]
+    fn lenient_parsing_only_needs_a_type_prefix() {
+        let known = |t: &str| STANDARD_TYPES.contains(&t.to_lowercase().as_str());
+        assert_eq!(
+            parse_lenient("feat(ui)!:missing space", known),
+            Some(("feat".to_string(), Some("ui".to_string()), true))
+        );
+        assert_eq!(
+            parse_lenient("fix: x\n\nBREAKING CHANGE: y", known),
+            Some(("fix".to_string(), None, true))
+        );
+        assert_eq!(
+            parse_lenient("Docs: typo", known),
+            Some(("Docs".to_string(), None, false))
+        );
+        assert_eq!(parse_lenient("Add users endpoint", known), None);
+    }
+    #[test]
+    fn lenient_parsing_ignores_unknown_prefixes() {
+        let known = |t: &str| STANDARD_TYPES.contains(&t) || t == "feature";
+        for message in [
+            "WIP: add users endpoint",
+            "Note: rotate keys",
+            "TODO(api): tests",
+        ] {
+            assert_eq!(
+                parse_lenient(message, known),
+                None,
+                "accepted `{}`",
+                message
+            );
+        }
+        assert_eq!(
+            parse_lenient("feature: add users endpoint", known),
+            Some(("feature".to_string(), None, false))
+        );
+    }
+    #[test]
+    fn keyword_guesses_match_whole_words() {
+        assert_eq!(guess_type("Fixed crash on start").as_deref(), Some("fix"));
+        assert_eq!(guess_type("Add new widget").as_deref(), Some("feat"));
+        assert_eq!(guess_type("Prefix the cache keys"), None);
+        assert_eq!(description("feat(api): add users"), "add users");
+    }
+}
@@ -1,7 +1,12 @@
 use crate::cli::SortOrder;
-use crate::config::Config;
+use crate::config::{Config, ConventionalConfig, Heuristics, NotesMode, TrailerConfig};
+use crate::conventional;
 use crate::error::{RcgenError, Result};
+use crate::expr::FilterExpr;
+use crate::overrides::Overrides;
+use crate::plugins;
 use crate::utils;
+use crate::version::Version;
 use chrono::Timelike;
 use chrono::{DateTime, FixedOffset};
 use git2::{Commit, Repository, Sort};
@@ -25,6 +30,25 @@ pub struct CommitInfo {
     pub branches: Vec<String>,
     pub commit_type: Option<String>,
     pub scope: Option<String>,
+    #[serde(default)]
+    pub trailers: Vec<Trailer>,
+    #[serde(default)]
+    pub breaking: bool,
+    #[serde(default)]
+    pub notes: Vec<String>,
+    #[serde(default)]
+    pub explicit_type: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Author {
@@ -65,9 +89,43 @@ pub struct AuthorStats {
     pub last_commit: DateTime<FixedOffset>,
     pub commit_types: HashMap<String, usize>,
 }
+// Which commits `get_commits` and `for_each_commit` return, and what they
+// include. `range` defaults to everything reachable from HEAD.
+#[derive(Debug, Clone)]
+pub struct CommitFilter<'a> {
+    pub range: Option<&'a str>,
+    pub limit: usize,
+    pub author: Option<&'a str>,
+    pub grep: Option<&'a str>,
+    pub expression: Option<&'a str>,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub include_body: bool,
+    pub sort_order: SortOrder,
+    pub exclude_merges: bool,
+    pub include_diff_stats: bool,
+}
+impl Default for CommitFilter<'_> {
+    fn default() -> Self {
+        Self {
+            range: None,
+            limit: 0,
+            author: None,
+            grep: None,
+            expression: None,
+            since: None,
+            until: None,
+            include_body: false,
+            sort_order: SortOrder::Reverse,
+            exclude_merges: false,
+            include_diff_stats: false,
+        }
+    }
+}
 pub struct GitAnalyzer {
     pub repo: Repository,
     pub config: Option<Config>,
+    pub overrides: Option<Overrides>,
 }
 
 impl GitAnalyzer {
@@ -75,62 +133,120 @@ impl GitAnalyzer {
         let repo = Repository::open(path)
             .map_err(|e| RcgenError::InvalidPath(format!("{}: {}", path, e)))?;
         let config = Config::load(path)?;
-        Ok(Self { repo, config })
+        let conventional = config
+            .as_ref()
+            .map(|config| config.conventional.clone())
+            .unwrap_or_default();
+        let overrides = Overrides::load(path, &repo, &conventional)?;
+        Ok(Self {
+            repo,
+            config,
+            overrides,
+        })
     }
-    pub fn get_commits(
-        &self,
-        limit: usize,
-        author_filter: Option<&str>,
-        grep_filter: Option<&str>,
-        since: Option<&str>,
-        until: Option<&str>,
-        include_body: bool,
-        _group_by_type: bool,
-        sort_order: SortOrder,
-        exclude_merges: bool,
-        include_diff_stats: bool,
-    ) -> Result<Vec<CommitInfo>> {
+    pub fn override_warnings(&self) -> Vec<String> {
+        self.overrides
+            .as_ref()
+            .map(|overrides| overrides.warnings().to_vec())
+            .unwrap_or_default()
+    }
+    pub fn commits_in_range(&self, range: &str) -> Result<Vec<git2::Oid>> {
+        if !range.contains("..") {
+            let commit = self
+                .repo
+                .revparse_single(range)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| RcgenError::InvalidRevision(format!("{}: {}", range, e)))?;
+            return Ok(vec![commit.id()]);
+        }
         let mut revwalk = self.repo.revwalk()?;
-        revwalk.push_head()?;
-        match sort_order {
+        revwalk
+            .push_range(range)
+            .map_err(|e| RcgenError::InvalidRevision(format!("{}: {}", range, e)))?;
+        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+        revwalk.map(|oid| oid.map_err(Into::into)).collect()
+    }
+    pub fn get_commits(&self, filter: &CommitFilter) -> Result<Vec<CommitInfo>> {
+        let mut commits = Vec::new();
+        self.for_each_commit(filter, |commit_info| {
+            commits.push(commit_info);
+            Ok(())
+        })?;
+        if let SortOrder::Author = filter.sort_order {
+            commits.sort_by(|a, b| a.author.name.cmp(&b.author.name));
+        }
+        Ok(commits)
+    }
+    // Hands each commit to `visit` as soon as it passes the filters. Commits
+    // come in revwalk order, so `SortOrder::Author` leaves them unsorted.
+    pub fn for_each_commit(
+        &self,
+        filter: &CommitFilter,
+        mut visit: impl FnMut(CommitInfo) -> Result<()>,
+    ) -> Result<()> {
+        let mut revwalk = self.revwalk(filter.range)?;
+        match filter.sort_order {
             SortOrder::Chronological => revwalk.set_sorting(Sort::TIME)?,
             SortOrder::Reverse => revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?,
             SortOrder::Author => revwalk.set_sorting(Sort::NONE)?,
         }
-        let author_regex = author_filter.map(|f| Regex::new(f)).transpose()?;
-        let grep_regex = grep_filter.map(|f| Regex::new(f)).transpose()?;
-        let since_time = since.and_then(|s| utils::parse_date(s).ok());
-        let until_time = until.and_then(|s| utils::parse_date(s).ok());
-        let mut commits = Vec::new();
+        let author_regex = filter.author.map(Regex::new).transpose()?;
+        let grep_regex = filter.grep.map(Regex::new).transpose()?;
+        let mut expressions = Vec::new();
+        if let Some(source) = filter.expression {
+            expressions.push(FilterExpr::parse(source)?);
+        }
+        if let Some(source) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.filters.expression.as_deref())
+        {
+            expressions.push(FilterExpr::parse(source)?);
+        }
+        let since_time = filter.since.and_then(|s| utils::parse_date(s).ok());
+        let until_time = filter.until.and_then(|s| utils::parse_date(s).ok());
         let mut count = 0;
         for oid in revwalk {
             let oid = oid?;
             let commit = self.repo.find_commit(oid)?;
-            if exclude_merges && commit.parent_count() > 1 {
+            if filter.exclude_merges && commit.parent_count() > 1 {
                 continue;
             }
-            let commit_info = self.process_commit(&commit, include_body, include_diff_stats)?;
-            if let Some(regex) = &author_regex {
-                if !regex.is_match(&commit_info.author.name)
-                    && !regex.is_match(&commit_info.author.email)
-                {
-                    continue;
-                }
+            let commit_info = match self.process_commit(
+                &commit,
+                filter.include_body,
+                filter.include_diff_stats,
+            )? {
+                Some(commit_info) => commit_info,
+                None => continue,
+            };
+            let commit_info = match &self.overrides {
+                Some(overrides) => match overrides.apply(commit_info) {
+                    Some(commit_info) => commit_info,
+                    None => continue,
+                },
+                None => commit_info,
+            };
+            if let Some(regex) = &author_regex
+                && !regex.is_match(&commit_info.author.name)
+                && !regex.is_match(&commit_info.author.email)
+            {
+                continue;
             }
-            if let Some(regex) = &grep_regex {
-                if !regex.is_match(&commit_info.message) {
-                    continue;
-                }
+            if let Some(regex) = &grep_regex
+                && !regex.is_match(&commit_info.message)
+            {
+                continue;
             }
-            if let Some(since) = since_time {
-                if commit_info.date < since {
-                    continue;
-                }
+            if let Some(since) = since_time
+                && commit_info.date < since
+            {
+                continue;
             }
-            if let Some(until) = until_time {
-                if commit_info.date > until {
-                    continue;
-                }
+            if let Some(until) = until_time
+                && commit_info.date > until
+            {
+                continue;
             }
             if let Some(config) = &self.config {
                 if config
@@ -146,11 +262,11 @@ impl GitAnalyzer {
                 }
                 let mut excluded = false;
                 for pattern in &config.filters.exclude_patterns {
-                    if let Ok(regex) = Regex::new(pattern) {
-                        if regex.is_match(&commit_info.summary) {
-                            excluded = true;
-                            break;
-                        }
+                    if let Ok(regex) = Regex::new(pattern)
+                        && regex.is_match(&commit_info.summary)
+                    {
+                        excluded = true;
+                        break;
                     }
                 }
                 if excluded {
@@ -159,11 +275,11 @@ impl GitAnalyzer {
                 if !config.filters.include_patterns.is_empty() {
                     let mut included = false;
                     for pattern in &config.filters.include_patterns {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if regex.is_match(&commit_info.summary) {
-                                included = true;
-                                break;
-                            }
+                        if let Ok(regex) = Regex::new(pattern)
+                            && regex.is_match(&commit_info.summary)
+                        {
+                            included = true;
+                            break;
                         }
                     }
                     if !included {
@@ -171,22 +287,101 @@ impl GitAnalyzer {
                     }
                 }
             }
-            commits.push(commit_info);
+            let mut rejected = false;
+            for expression in &expressions {
+                if !expression.matches(&commit_info)? {
+                    rejected = true;
+                    break;
+                }
+            }
+            if rejected {
+                continue;
+            }
+            let commit_info = match self.apply_filter_plugins(commit_info)? {
+                Some(commit_info) => commit_info,
+                None => continue,
+            };
+            visit(commit_info)?;
             count += 1;
-            if limit > 0 && count >= limit {
+            if filter.limit > 0 && count >= filter.limit {
                 break;
             }
         }
-        if let SortOrder::Author = sort_order {
-            commits.sort_by(|a, b| a.author.name.cmp(&b.author.name));
+        Ok(())
+    }
+    fn apply_filter_plugins(&self, commit_info: CommitInfo) -> Result<Option<CommitInfo>> {
+        let Some(config) = &self.config else {
+            return Ok(Some(commit_info));
+        };
+        let mut current = commit_info;
+        for plugin in &config.plugins.filters {
+            match plugins::run_filter(plugin, self.repo.workdir(), &current)? {
+                Some(updated) => current = updated,
+                None => return Ok(None),
+            }
         }
-        Ok(commits)
+        Ok(Some(current))
+    }
+    fn revwalk(&self, range: Option<&str>) -> Result<git2::Revwalk<'_>> {
+        let mut revwalk = self.repo.revwalk()?;
+        match range {
+            Some(range) if range.contains("..") => revwalk
+                .push_range(range)
+                .map_err(|e| RcgenError::InvalidRevision(format!("{}: {}", range, e)))?,
+            Some(rev) => {
+                let commit = self
+                    .repo
+                    .revparse_single(rev)
+                    .and_then(|obj| obj.peel_to_commit())
+                    .map_err(|e| RcgenError::InvalidRevision(format!("{}: {}", rev, e)))?;
+                revwalk.push(commit.id())?;
+            }
+            None => revwalk.push_head()?,
+        }
+        Ok(revwalk)
+    }
+    // Walks every commit in `range` (default: reachable from HEAD), oldest
+    // first, as git records it: filters, overrides, changelog trailers and
+    // notes and filter plugins are all ignored.
+    pub fn for_each_raw_commit(
+        &self,
+        range: Option<&str>,
+        include_body: bool,
+        include_diff_stats: bool,
+        mut visit: impl FnMut(CommitInfo) -> Result<()>,
+    ) -> Result<()> {
+        let mut revwalk = self.revwalk(range)?;
+        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            visit(self.raw_commit(&commit, include_body, include_diff_stats)?)?;
+        }
+        Ok(())
     }
     fn process_commit(
         &self,
         commit: &Commit,
         include_body: bool,
         include_diff_stats: bool,
+    ) -> Result<Option<CommitInfo>> {
+        let mut commit_info = self.raw_commit(commit, include_body, include_diff_stats)?;
+        if !self.apply_changelog_trailers(&mut commit_info) {
+            return Ok(None);
+        }
+        if let Some(note) = self.read_changelog_note(commit.id()) {
+            let directives = ChangelogNote::parse(&note);
+            if directives.skip {
+                return Ok(None);
+            }
+            self.apply_changelog_note(&mut commit_info, directives);
+        }
+        Ok(Some(commit_info))
+    }
+    fn raw_commit(
+        &self,
+        commit: &Commit,
+        include_body: bool,
+        include_diff_stats: bool,
     ) -> Result<CommitInfo> {
         let hash = commit.id().to_string();
         let short_hash = hash.chars().take(8).collect::<String>();
@@ -194,12 +389,7 @@ impl GitAnalyzer {
         let author_name = author.name().unwrap_or("Unknown").to_string();
         let author_email = author.email().unwrap_or("unknown").to_string();
         let time = commit.time();
-        let date = DateTime::from_timestamp(time.seconds(), 0)
-            .ok_or_else(|| RcgenError::DateParse("Invalid timestamp".to_string()))?
-            .with_timezone(
-                &FixedOffset::east_opt(time.offset_minutes() * 60)
-                    .ok_or_else(|| RcgenError::DateParse("Invalid offset".to_string()))?,
-            );
+        let date = utils::git_time(time)?;
         let full_message = commit.message().unwrap_or("").to_string();
         let (summary, body) = if include_body {
             if let Some(pos) = full_message.find("\n\n") {
@@ -224,7 +414,8 @@ impl GitAnalyzer {
         };
         let tags = self.get_commit_tags(&hash)?;
         let branches = self.get_commit_branches(commit)?;
-        let (commit_type, scope) = self.detect_commit_type(&summary);
+        let (commit_type, scope, breaking, explicit_type) = self.detect_commit_type(&full_message);
+        let trailers = parse_trailers(&full_message);
         Ok(CommitInfo {
             hash,
             short_hash,
@@ -245,40 +436,116 @@ impl GitAnalyzer {
             branches,
             commit_type,
             scope,
+            trailers,
+            breaking,
+            notes: Vec::new(),
+            explicit_type,
         })
     }
-    fn get_commit_stats(&self, commit: &Commit) -> Result<(Vec<String>, usize, usize)> {
-        let mut files = Vec::new();
-        let mut insertions = 0;
-        let mut deletions = 0;
-        if commit.parent_count() > 0 {
-            let parent = commit.parent(0)?;
-            let tree1 = parent.tree()?;
-            let tree2 = commit.tree()?;
-            let diff = self
-                .repo
-                .diff_tree_to_tree(Some(&tree1), Some(&tree2), None)?;
-            diff.foreach(
-                &mut |delta, _| {
-                    if let Some(file) = delta.new_file().path() {
-                        files.push(file.to_string_lossy().to_string());
-                    }
-                    true
-                },
-                None,
-                None,
-                Some(&mut |_delta, _hunk, line| {
-                    match line.origin() {
-                        '+' => insertions += 1,
-                        '-' => deletions += 1,
-                        _ => {}
-                    }
-                    true
-                }),
-            )?;
+    fn apply_changelog_trailers(&self, commit_info: &mut CommitInfo) -> bool {
+        let default_names = TrailerConfig::default();
+        let names = self
+            .config
+            .as_ref()
+            .map(|c| &c.trailers)
+            .unwrap_or(&default_names);
+        let find = |name: &str| {
+            commit_info
+                .trailers
+                .iter()
+                .rev()
+                .find(|t| t.key.eq_ignore_ascii_case(name))
+                .map(|t| t.value.clone())
+        };
+        if find(&names.skip).is_some_and(|v| v.eq_ignore_ascii_case("skip")) {
+            return false;
+        }
+        let commit_type = find(&names.commit_type);
+        let scope = find(&names.scope);
+        let entry = find(&names.entry);
+        if let Some(commit_type) = commit_type {
+            commit_info.commit_type = Some(commit_type.to_lowercase());
+            commit_info.explicit_type = true;
+        }
+        if let Some(scope) = scope {
+            commit_info.scope = Some(scope);
+        }
+        if let Some(entry) = entry {
+            commit_info.summary = entry;
+        }
+        true
+    }
+    fn read_changelog_note(&self, oid: git2::Oid) -> Option<String> {
+        let notes_ref = self.config.as_ref()?.notes.notes_ref.as_deref()?;
+        let notes_ref = if notes_ref.starts_with("refs/") {
+            notes_ref.to_string()
+        } else {
+            format!("refs/notes/{}", notes_ref)
+        };
+        let note = self.repo.find_note(Some(&notes_ref), oid).ok()?;
+        note.message().map(|m| m.to_string())
+    }
+    fn apply_changelog_note(&self, commit_info: &mut CommitInfo, note: ChangelogNote) {
+        if let Some(commit_type) = note.commit_type {
+            commit_info.commit_type = Some(commit_type);
+            commit_info.explicit_type = true;
+        }
+        if let Some(scope) = note.scope {
+            commit_info.scope = Some(scope);
+        }
+        if note.breaking {
+            commit_info.breaking = true;
+        }
+        if let Some(text) = note.text {
+            let mode = self
+                .config
+                .as_ref()
+                .map(|c| c.notes.mode)
+                .unwrap_or_default();
+            match mode {
+                NotesMode::Replace => commit_info.summary = text,
+                NotesMode::Append => commit_info.notes.push(text),
+            }
         }
+    }
+    fn get_commit_stats(&self, commit: &Commit) -> Result<(Vec<String>, usize, usize)> {
+        let changes = self.get_file_changes(commit)?;
+        let insertions = changes.iter().map(|c| c.insertions).sum();
+        let deletions = changes.iter().map(|c| c.deletions).sum();
+        let files = changes.into_iter().map(|c| c.path).collect();
         Ok((files, insertions, deletions))
     }
+    pub fn get_file_changes(&self, commit: &Commit) -> Result<Vec<FileChange>> {
+        // A root commit is diffed against the empty tree, so every file counts
+        // as added.
+        let parent_tree = match commit.parent_count() {
+            0 => None,
+            _ => Some(commit.parent(0)?.tree()?),
+        };
+        let tree = commit.tree()?;
+        let diff = self
+            .repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let mut changes = Vec::new();
+        for (index, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta.new_file().path() else {
+                continue;
+            };
+            let (insertions, deletions) = match git2::Patch::from_diff(&diff, index)? {
+                Some(patch) => {
+                    let (_, insertions, deletions) = patch.line_stats()?;
+                    (insertions, deletions)
+                }
+                None => (0, 0),
+            };
+            changes.push(FileChange {
+                path: path.to_string_lossy().to_string(),
+                insertions,
+                deletions,
+            });
+        }
+        Ok(changes)
+    }
     fn get_commit_tags(&self, hash: &str) -> Result<Vec<String>> {
         let mut tags = Vec::new();
         let oid = git2::Oid::from_str(hash)?;
@@ -290,6 +557,8 @@ impl GitAnalyzer {
                 tags.push(name.to_string());
             }
         }
+        // tag_names is lexical, which puts v1.10.0 before v1.9.0.
+        tags.sort_by_cached_key(|tag| (Version::from_tag(tag, None).map(|(_, v)| v), tag.clone()));
         Ok(tags)
     }
     fn get_commit_branches(&self, commit: &Commit) -> Result<Vec<String>> {
@@ -311,38 +580,62 @@ impl GitAnalyzer {
         }
         Ok(branches)
     }
-    fn detect_commit_type(&self, summary: &str) -> (Option<String>, Option<String>) {
-        let conventional_pattern = Regex::new(r"^(\w+)(?:\(([^)]+)\))?!?:").unwrap();
-        if let Some(caps) = conventional_pattern.captures(summary) {
-            let commit_type = caps.get(1).map(|m| m.as_str().to_string());
-            let scope = caps.get(2).map(|m| m.as_str().to_string());
-            return (commit_type, scope);
-        }
-        let summary_lower = summary.to_lowercase();
-        let commit_type = if summary_lower.contains("fix") || summary_lower.contains("bug") {
-            Some("fix".to_string())
-        } else if summary_lower.contains("feat")
-            || summary_lower.contains("add")
-            || summary_lower.contains("new")
-        {
-            Some("feat".to_string())
-        } else if summary_lower.contains("doc") {
-            Some("docs".to_string())
-        } else if summary_lower.contains("test") {
-            Some("test".to_string())
-        } else if summary_lower.contains("refactor") {
-            Some("refactor".to_string())
-        } else if summary_lower.contains("perf") {
-            Some("perf".to_string())
-        } else if summary_lower.contains("chore")
-            || summary_lower.contains("ci")
-            || summary_lower.contains("build")
-        {
-            Some("chore".to_string())
-        } else {
-            None
-        };
-        (commit_type, None)
+    // The last element tells whether the type was written by the author in a
+    // Conventional Commits header rather than guessed from keywords.
+    fn detect_commit_type(&self, message: &str) -> (Option<String>, Option<String>, bool, bool) {
+        let default_settings = ConventionalConfig::default();
+        let settings = self.conventional_settings(&default_settings);
+        if settings.heuristics == Heuristics::Strict {
+            return match conventional::parse(message) {
+                Ok(parsed) => (
+                    Some(settings.normalize_type(&parsed.commit_type)),
+                    parsed.scope,
+                    parsed.breaking,
+                    true,
+                ),
+                Err(_) => (None, None, false, false),
+            };
+        }
+        let known = |commit_type: &str| self.is_known_type(settings, commit_type);
+        if let Some((commit_type, scope, breaking)) = conventional::parse_lenient(message, known) {
+            return (
+                Some(settings.normalize_type(&commit_type)),
+                scope,
+                breaking,
+                true,
+            );
+        }
+        if settings.heuristics == Heuristics::Keywords {
+            let summary = message.lines().next().unwrap_or("");
+            return (conventional::guess_type(summary), None, false, false);
+        }
+        (None, None, false, false)
+    }
+    // Standard types, alias targets and the types the grouping patterns name.
+    fn is_known_type(&self, settings: &ConventionalConfig, commit_type: &str) -> bool {
+        let commit_type = settings.normalize_type(commit_type);
+        conventional::STANDARD_TYPES.contains(&commit_type.as_str())
+            || settings
+                .aliases
+                .values()
+                .any(|t| t.eq_ignore_ascii_case(&commit_type))
+            || self.config.as_ref().is_some_and(|config| {
+                config
+                    .grouping
+                    .groups
+                    .iter()
+                    .flat_map(|g| g.types())
+                    .any(|t| settings.normalize_type(t) == commit_type)
+            })
+    }
+    fn conventional_settings<'a>(
+        &'a self,
+        default: &'a ConventionalConfig,
+    ) -> &'a ConventionalConfig {
+        self.config
+            .as_ref()
+            .map(|c| &c.conventional)
+            .unwrap_or(default)
     }
     pub fn group_commits(&self, commits: &[CommitInfo]) -> Vec<CommitGroup> {
         let mut groups: HashMap<String, Vec<CommitInfo>> = HashMap::new();
@@ -362,54 +655,45 @@ impl GitAnalyzer {
             "Other",
         ];
         for name in default_groups {
-            groups.entry(name.to_string()).or_insert_with(Vec::new);
+            groups.entry(name.to_string()).or_default();
         }
+        let default_settings = ConventionalConfig::default();
+        let settings = self.conventional_settings(&default_settings);
         for commit in commits {
-            let mut placed = false;
-            if let Some(config) = &self.config {
-                for group in &config.grouping.groups {
-                    for pattern in &group.patterns {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if regex.is_match(&commit.summary) {
-                                groups
-                                    .entry(group.name.clone())
-                                    .or_default()
-                                    .push(commit.clone());
-                                placed = true;
-                                break;
-                            }
-                        }
-                    }
-                    if placed {
-                        break;
-                    }
-                }
-            }
-            if !placed {
-                if let Some(commit_type) = &commit.commit_type {
-                    let group_name = match commit_type.as_str() {
-                        "feat" => "Features",
-                        "fix" => "Bug Fixes",
-                        "docs" => "Documentation",
-                        "refactor" => "Refactoring",
-                        "perf" => "Performance",
-                        "test" => "Tests",
-                        "chore" => "Chores",
-                        _ => "Other",
-                    };
-                    groups
-                        .entry(group_name.to_string())
-                        .or_default()
-                        .push(commit.clone());
-                    placed = true;
-                }
-            }
-            if !placed {
-                groups
-                    .entry("Other".to_string())
-                    .or_default()
-                    .push(commit.clone());
-            }
+            let group_name = match &commit.commit_type {
+                // A type from a Conventional Commits header, a note, a trailer
+                // or an override is used as is; the summary patterns are only
+                // a fallback for guessing.
+                Some(commit_type) if commit.explicit_type => self
+                    .config
+                    .as_ref()
+                    .and_then(|config| {
+                        config.grouping.groups.iter().find(|group| {
+                            group
+                                .types()
+                                .any(|t| settings.normalize_type(t) == *commit_type)
+                        })
+                    })
+                    .map(|group| group.name.clone())
+                    .unwrap_or_else(|| default_group(commit_type).to_string()),
+                _ if settings.heuristics == Heuristics::Keywords => self
+                    .config
+                    .as_ref()
+                    .and_then(|config| {
+                        config.grouping.groups.iter().find(|group| {
+                            group.patterns.iter().any(|pattern| {
+                                Regex::new(pattern)
+                                    .is_ok_and(|regex| regex.is_match(&commit.summary))
+                            })
+                        })
+                    })
+                    .map(|group| group.name.clone())
+                    .unwrap_or_else(|| {
+                        default_group(commit.commit_type.as_deref().unwrap_or("")).to_string()
+                    }),
+                _ => "Other".to_string(),
+            };
+            groups.entry(group_name).or_default().push(commit.clone());
         }
         let mut result = Vec::new();
         for (name, commits) in groups {
@@ -431,7 +715,7 @@ impl GitAnalyzer {
                 });
             }
         }
-        let group_order = vec![
+        let group_order = [
             "Features",
             "Bug Fixes",
             "Documentation",
@@ -537,7 +821,7 @@ impl GitAnalyzer {
             0.0
         };
         let mut authors_vec: Vec<AuthorStats> = authors.into_values().collect();
-        authors_vec.sort_by(|a, b| b.commits.cmp(&a.commits));
+        authors_vec.sort_by_key(|a| std::cmp::Reverse(a.commits));
         RepositoryStats {
             total_commits: commits.len(),
             total_authors: authors_vec.len(),
@@ -556,3 +840,77 @@ impl GitAnalyzer {
         }
     }
 }
+#[derive(Debug, Default)]
+struct ChangelogNote {
+    skip: bool,
+    breaking: bool,
+    commit_type: Option<String>,
+    scope: Option<String>,
+    text: Option<String>,
+}
+impl ChangelogNote {
+    fn parse(note: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut text = Vec::new();
+        let directive = |line: &str, name: &str| -> Option<String> {
+            let prefix = line.get(..name.len())?;
+            prefix
+                .eq_ignore_ascii_case(name)
+                .then(|| line[name.len()..].trim().to_string())
+        };
+        for line in note.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if line.eq_ignore_ascii_case("skip") {
+                parsed.skip = true;
+            } else if line.eq_ignore_ascii_case("breaking") {
+                parsed.breaking = true;
+            } else if let Some(value) = directive(line, "type:") {
+                parsed.commit_type = Some(value.to_lowercase());
+            } else if let Some(value) = directive(line, "scope:") {
+                parsed.scope = Some(value);
+            } else {
+                text.push(line);
+            }
+        }
+        if !text.is_empty() {
+            parsed.text = Some(text.join(" "));
+        }
+        parsed
+    }
+}
+pub fn parse_trailers(message: &str) -> Vec<Trailer> {
+    let trailer_line = Regex::new(r"^([A-Za-z0-9][A-Za-z0-9-]*):\s*(.*)$").unwrap();
+    let paragraphs: Vec<&str> = message.trim().split("\n\n").collect();
+    // The subject paragraph can never hold trailers.
+    if paragraphs.len() < 2 {
+        return Vec::new();
+    }
+    let mut trailers: Vec<Trailer> = Vec::new();
+    for line in paragraphs[paragraphs.len() - 1].lines() {
+        if let Some(caps) = trailer_line.captures(line) {
+            trailers.push(Trailer {
+                key: caps[1].to_string(),
+                value: caps[2].trim().to_string(),
+            });
+        } else if line.starts_with(char::is_whitespace)
+            && let Some(last) = trailers.last_mut()
+        {
+            last.value.push(' ');
+            last.value.push_str(line.trim());
+        } else {
+            return Vec::new();
+        }
+    }
+    trailers
+}
+fn default_group(commit_type: &str) -> &'static str {
+    match commit_type {
+        "feat" => "Features",
+        "fix" => "Bug Fixes",
+        "docs" => "Documentation",
+        "refactor" => "Refactoring",
+        "perf" => "Performance",
+        "test" => "Tests",
+        "chore" => "Chores",
+        _ => "Other",
+    }
+}
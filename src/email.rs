@@ -0,0 +1,304 @@
+use crate::feed::Person;
+use chrono::{DateTime, FixedOffset, Utc};
+// Quoted-printable lines may be at most 76 characters, including the `=` of a
+// soft line break.
+const QP_LINE: usize = 76;
+// An RFC 2047 encoded word may be at most 75 characters long, and header
+// lines should stay within 78.
+const ENCODED_WORD: usize = 75;
+const HEADER_LINE: usize = 78;
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub date: DateTime<FixedOffset>,
+    pub message_id: String,
+    pub text: String,
+    pub html: String,
+}
+// Lines end in LF, as sendmail, git send-email and mbox files expect them on
+// Unix; the MTA converts them to CRLF on the wire.
+pub fn eml(message: &Message) -> String {
+    let boundary = format!("=_rcgen_{}", message.date.timestamp());
+    let mut output = String::new();
+    output.push_str(&format!("From: {}\n", address(&message.from)));
+    output.push_str(&format!("To: {}\n", address_list(&message.to)));
+    if !message.cc.is_empty() {
+        output.push_str(&format!("Cc: {}\n", address_list(&message.cc)));
+    }
+    output.push_str(&format!(
+        "Subject: {}\n",
+        header_text(&message.subject, "Subject: ".len())
+    ));
+    output.push_str(&format!("Date: {}\n", message.date.to_rfc2822()));
+    output.push_str(&format!("Message-ID: <{}>\n", message.message_id));
+    output.push_str("MIME-Version: 1.0\n");
+    output.push_str(&format!(
+        "Content-Type: multipart/alternative; boundary=\"{}\"\n\n",
+        boundary
+    ));
+    output.push_str("This is a multi-part message in MIME format.\n");
+    // The boundary starts with `=_`, which never occurs in quoted-printable
+    // text, so the parts cannot contain it.
+    for (subtype, body) in [("plain", &message.text), ("html", &message.html)] {
+        output.push_str(&format!("\n--{}\n", boundary));
+        output.push_str(&format!(
+            "Content-Type: text/{}; charset=utf-8\nContent-Transfer-Encoding: quoted-printable\n\n",
+            subtype
+        ));
+        output.push_str(&quoted_printable(body));
+    }
+    output.push_str(&format!("\n--{}--\n", boundary));
+    output
+}
+// A single-message mbox in the mboxrd variant, so it can be appended to an
+// existing mailbox or handed to `git send-email`.
+pub fn mbox(message: &Message) -> String {
+    let sender = Person::parse(&message.from)
+        .email
+        .unwrap_or_else(|| "MAILER-DAEMON".to_string());
+    let mut output = format!(
+        "From {} {}\n",
+        sender,
+        message
+            .date
+            .with_timezone(&Utc)
+            .format("%a %b %e %H:%M:%S %Y")
+    );
+    for line in eml(message).lines() {
+        if line.trim_start_matches('>').starts_with("From ") {
+            output.push('>');
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push('\n');
+    output
+}
+fn address_list(values: &[String]) -> String {
+    let addresses: Vec<String> = values.iter().map(|value| address(value)).collect();
+    addresses.join(",\n ")
+}
+fn address(value: &str) -> String {
+    let person = Person::parse(value);
+    let Some(email) = person.email else {
+        return person.name;
+    };
+    if person.name.is_empty() {
+        return email;
+    }
+    let name = if !person.name.is_ascii() {
+        encoded_words(&person.name, 1)
+    } else if person
+        .name
+        .contains(|c: char| "()<>[]:;@\\,.\"".contains(c))
+    {
+        format!(
+            "\"{}\"",
+            person.name.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    } else {
+        person.name
+    };
+    format!("{} <{}>", name, email)
+}
+fn header_text(text: &str, indent: usize) -> String {
+    if text.is_ascii() {
+        text.to_string()
+    } else {
+        encoded_words(text, indent)
+    }
+}
+// RFC 2047 "Q" encoding. Characters are never split across words, since each
+// word has to be valid UTF-8 on its own. `indent` is the width of whatever
+// precedes the first word on its line.
+fn encoded_words(text: &str, indent: usize) -> String {
+    const PREFIX: &str = "=?utf-8?Q?";
+    const SUFFIX: &str = "?=";
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        let encoded = match c {
+            ' ' => "_".to_string(),
+            c if c.is_ascii_alphanumeric() || "!*+-/".contains(c) => c.to_string(),
+            c => {
+                let mut buffer = [0; 4];
+                c.encode_utf8(&mut buffer)
+                    .bytes()
+                    .map(|b| format!("={:02X}", b))
+                    .collect()
+            }
+        };
+        let limit = match words.len() {
+            0 => ENCODED_WORD.min(HEADER_LINE.saturating_sub(indent)),
+            _ => ENCODED_WORD,
+        };
+        if PREFIX.len() + word.len() + encoded.len() + SUFFIX.len() > limit {
+            words.push(format!("{}{}{}", PREFIX, word, SUFFIX));
+            word.clear();
+        }
+        word.push_str(&encoded);
+    }
+    words.push(format!("{}{}{}", PREFIX, word, SUFFIX));
+    words.join("\n ")
+}
+fn quoted_printable(text: &str) -> String {
+    let mut output = String::new();
+    for line in text.lines() {
+        let bytes = line.as_bytes();
+        let mut length = 0;
+        for (index, &byte) in bytes.iter().enumerate() {
+            let last = index + 1 == bytes.len();
+            // Trailing whitespace would be stripped in transit, and a leading
+            // dot can end the message when it is piped to sendmail.
+            let encode = |length: usize| {
+                let literal = match byte {
+                    b'=' => false,
+                    b' ' | b'\t' => !last,
+                    b'.' => length > 0,
+                    33..=126 => true,
+                    _ => false,
+                };
+                if literal {
+                    (byte as char).to_string()
+                } else {
+                    format!("={:02X}", byte)
+                }
+            };
+            // The soft break needs one more column, unless this is the last
+            // character of the line.
+            let room = if last { QP_LINE } else { QP_LINE - 1 };
+            let mut encoded = encode(length);
+            if length + encoded.len() > room {
+                output.push_str("=\n");
+                length = 0;
+                encoded = encode(length);
+            }
+            output.push_str(&encoded);
+            length += encoded.len();
+        }
+        output.push('\n');
+    }
+    output
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn decode_quoted_printable(encoded: &str) -> Vec<u8> {
+        let joined = encoded.replace("=\n", "");
+        let mut bytes = Vec::new();
+        let mut rest = joined.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'=' {
+                let hex = std::str::from_utf8(&tail[..2]).unwrap();
+                bytes.push(u8::from_str_radix(hex, 16).unwrap());
+                rest = &tail[2..];
+            } else {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+        bytes
+    }
+    fn decode_encoded_word(word: &str) -> Vec<u8> {
+        let text = word
+            .strip_prefix("=?utf-8?Q?")
+            .and_then(|w| w.strip_suffix("?="))
+            .unwrap();
+        decode_quoted_printable(&text.replace('_', " "))
+    }
+    fn message(text: &str) -> Message {
+        Message {
+            from: "Release Bot <bot@example.com>".to_string(),
+            to: vec!["dev@example.com".to_string()],
+            cc: Vec::new(),
+            subject: "widget 1.2.0".to_string(),
+            date: DateTime::parse_from_rfc3339("2024-03-01T10:00:00+00:00").unwrap(),
+            message_id: "1.2.0@example.com".to_string(),
+            text: text.to_string(),
+            html: String::new(),
+        }
+    }
+    #[test]
+    fn quoted_printable_wraps_long_lines_with_soft_breaks() {
+        let text = format!("{}\n{}", "a=b ".repeat(40), "é".repeat(30));
+        let encoded = quoted_printable(&text);
+        assert!(encoded.lines().count() > 2);
+        for line in encoded.lines() {
+            assert!(line.len() <= QP_LINE, "{} is too long", line);
+        }
+        assert_eq!(
+            String::from_utf8(decode_quoted_printable(&encoded)).unwrap(),
+            format!("{}\n", text)
+        );
+    }
+    #[test]
+    fn quoted_printable_never_splits_an_escape() {
+        let encoded = quoted_printable(&format!("{}é", "a".repeat(73)));
+        assert_eq!(encoded, format!("{}=\n=C3=A9\n", "a".repeat(73)));
+    }
+    #[test]
+    fn quoted_printable_encodes_leading_dots() {
+        assert_eq!(quoted_printable(".\n.hidden\na.b"), "=2E\n=2Ehidden\na.b\n");
+        // A dot that would start the line after a soft break is encoded too.
+        let encoded = quoted_printable(&format!("{}.tail", "a".repeat(75)));
+        assert_eq!(encoded, format!("{}=\n=2Etail\n", "a".repeat(75)));
+    }
+    #[test]
+    fn quoted_printable_encodes_trailing_whitespace() {
+        assert_eq!(
+            quoted_printable("end \ntab\t\na b"),
+            "end=20\ntab=09\na b\n"
+        );
+    }
+    #[test]
+    fn encoded_words_respect_length_limits_and_character_boundaries() {
+        let subject = "Version 2.0 — überarbeitete Oberfläche für alle Benutzer 🎉🎉🎉 und mehr";
+        let encoded = header_text(subject, "Subject: ".len());
+        let words: Vec<&str> = encoded.split("\n ").collect();
+        assert!(words.len() > 1);
+        assert!("Subject: ".len() + words[0].len() <= HEADER_LINE);
+        let mut decoded = Vec::new();
+        for word in &words {
+            assert!(word.len() <= ENCODED_WORD, "{} is too long", word);
+            let bytes = decode_encoded_word(word);
+            assert!(
+                String::from_utf8(bytes.clone()).is_ok(),
+                "{} splits a character",
+                word
+            );
+            decoded.extend(bytes);
+        }
+        assert_eq!(String::from_utf8(decoded).unwrap(), subject);
+    }
+    #[test]
+    fn ascii_headers_are_left_alone() {
+        assert_eq!(header_text("widget 1.2.0", 9), "widget 1.2.0");
+    }
+    #[test]
+    fn addresses_quote_or_encode_display_names() {
+        assert_eq!(
+            address("Jane Doe <jane@example.com>"),
+            "Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            address("Doe, Jane \"JD\" <jane@example.com>"),
+            "\"Doe, Jane \\\"JD\\\"\" <jane@example.com>"
+        );
+        assert_eq!(
+            address("José <jose@example.com>"),
+            "=?utf-8?Q?Jos=C3=A9?= <jose@example.com>"
+        );
+        assert_eq!(address("jane@example.com"), "jane@example.com");
+    }
+    #[test]
+    fn mbox_escapes_from_lines() {
+        let output = mbox(&message("From the changelog\n>From quoted"));
+        assert!(output.starts_with("From bot@example.com Fri Mar  1 10:00:00 2024\n"));
+        assert!(output.contains("\n>From the changelog\n"));
+        assert!(output.contains("\n>>From quoted\n"));
+        assert!(output.ends_with("--\n\n"));
+    }
+}
@@ -19,5 +19,15 @@ pub enum RcgenError {
     NoCommits,
     #[error("Invalid revision: {0}")]
     InvalidRevision(String),
+    #[error("Invalid filter expression: {0}")]
+    FilterExpr(String),
+    #[error("Plugin error: {0}")]
+    Plugin(String),
+    #[error("Plugin '{0}' timed out after {1}s")]
+    PluginTimeout(String, u64),
+    #[error("Release error: {0}")]
+    Release(String),
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
 }
 pub type Result<T> = std::result::Result<T, RcgenError>;
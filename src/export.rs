@@ -0,0 +1,274 @@
+use crate::core::{CommitInfo, GitAnalyzer};
+use crate::error::Result;
+use crate::release;
+use rusqlite::{Connection, Transaction, params};
+use std::collections::HashSet;
+use std::path::Path;
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS authors (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    UNIQUE (name, email)
+);
+CREATE TABLE IF NOT EXISTS commit_types (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS commits (
+    hash TEXT PRIMARY KEY,
+    short_hash TEXT NOT NULL,
+    author_id INTEGER NOT NULL REFERENCES authors (id),
+    date TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    body TEXT,
+    type_id INTEGER REFERENCES commit_types (id),
+    scope TEXT,
+    breaking INTEGER NOT NULL,
+    is_merge INTEGER NOT NULL,
+    insertions INTEGER NOT NULL,
+    deletions INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS files_changed (
+    commit_hash TEXT NOT NULL REFERENCES commits (hash),
+    path TEXT NOT NULL,
+    insertions INTEGER NOT NULL,
+    deletions INTEGER NOT NULL,
+    PRIMARY KEY (commit_hash, path)
+);
+CREATE TABLE IF NOT EXISTS trailers (
+    commit_hash TEXT NOT NULL REFERENCES commits (hash),
+    key TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tags (
+    name TEXT PRIMARY KEY,
+    commit_hash TEXT NOT NULL,
+    annotated INTEGER NOT NULL,
+    tagger_name TEXT,
+    tagger_email TEXT,
+    date TEXT NOT NULL,
+    message TEXT
+);
+CREATE INDEX IF NOT EXISTS commits_author ON commits (author_id);
+CREATE INDEX IF NOT EXISTS commits_timestamp ON commits (timestamp);
+CREATE INDEX IF NOT EXISTS commits_type ON commits (type_id);
+CREATE INDEX IF NOT EXISTS files_changed_path ON files_changed (path);
+CREATE INDEX IF NOT EXISTS trailers_commit ON trailers (commit_hash);
+CREATE INDEX IF NOT EXISTS trailers_key ON trailers (key);
+CREATE INDEX IF NOT EXISTS tags_commit ON tags (commit_hash);
+";
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub added: usize,
+    pub existing: usize,
+    pub tags: usize,
+}
+pub fn export_sqlite(analyzer: &GitAnalyzer, path: &Path) -> Result<ExportSummary> {
+    let mut connection = Connection::open(path)?;
+    connection.execute_batch(SCHEMA)?;
+
+    let known: HashSet<String> = {
+        let mut statement = connection.prepare("SELECT hash FROM commits")?;
+        statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?
+    };
+
+    let transaction = connection.transaction()?;
+    let mut summary = ExportSummary::default();
+    // Commits that are already stored are skipped before their diff is
+    // computed, which is where most of the time goes.
+    analyzer.for_each_raw_commit(None, true, false, |commit| {
+        if known.contains(&commit.hash) {
+            summary.existing += 1;
+            return Ok(());
+        }
+        insert_commit(analyzer, &transaction, &commit)?;
+        summary.added += 1;
+        Ok(())
+    })?;
+    summary.tags = replace_tags(analyzer, &transaction)?;
+    transaction.commit()?;
+
+    Ok(summary)
+}
+fn insert_commit(
+    analyzer: &GitAnalyzer,
+    transaction: &Transaction,
+    commit: &CommitInfo,
+) -> Result<()> {
+    let author_id: i64 = transaction.query_row(
+        "INSERT INTO authors (name, email) VALUES (?1, ?2)
+         ON CONFLICT (name, email) DO UPDATE SET name = excluded.name
+         RETURNING id",
+        params![commit.author.name, commit.author.email],
+        |row| row.get(0),
+    )?;
+    let type_id: Option<i64> = match &commit.commit_type {
+        Some(commit_type) => Some(transaction.query_row(
+            "INSERT INTO commit_types (name) VALUES (?1)
+             ON CONFLICT (name) DO UPDATE SET name = excluded.name
+             RETURNING id",
+            params![commit_type],
+            |row| row.get(0),
+        )?),
+        None => None,
+    };
+
+    let git_commit = analyzer
+        .repo
+        .find_commit(git2::Oid::from_str(&commit.hash)?)?;
+    let changes = analyzer.get_file_changes(&git_commit)?;
+    let insertions: usize = changes.iter().map(|c| c.insertions).sum();
+    let deletions: usize = changes.iter().map(|c| c.deletions).sum();
+
+    transaction.execute(
+        "INSERT INTO commits (hash, short_hash, author_id, date, timestamp, summary, body,
+                              type_id, scope, breaking, is_merge, insertions, deletions)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            commit.hash,
+            commit.short_hash,
+            author_id,
+            commit.date.to_rfc3339(),
+            commit.date.timestamp(),
+            commit.summary,
+            commit.body,
+            type_id,
+            commit.scope,
+            commit.breaking,
+            commit.is_merge,
+            insertions as i64,
+            deletions as i64,
+        ],
+    )?;
+    for change in &changes {
+        // A type change, e.g. a file replaced by a symlink, shows up as a
+        // delete and an add of the same path, so the two are merged.
+        transaction.execute(
+            "INSERT INTO files_changed (commit_hash, path, insertions, deletions)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (commit_hash, path) DO UPDATE SET
+                 insertions = insertions + excluded.insertions,
+                 deletions = deletions + excluded.deletions",
+            params![
+                commit.hash,
+                change.path,
+                change.insertions as i64,
+                change.deletions as i64
+            ],
+        )?;
+    }
+    for trailer in &commit.trailers {
+        transaction.execute(
+            "INSERT INTO trailers (commit_hash, key, value) VALUES (?1, ?2, ?3)",
+            params![commit.hash, trailer.key, trailer.value],
+        )?;
+    }
+    Ok(())
+}
+fn replace_tags(analyzer: &GitAnalyzer, transaction: &Transaction) -> Result<usize> {
+    // Tags can move or disappear, so they are rewritten on every run.
+    transaction.execute("DELETE FROM tags", [])?;
+    let repo = &analyzer.repo;
+    let mut count = 0;
+    for name in repo.tag_names(None)?.iter().flatten() {
+        // Tags of trees or blobs have no place in the commit history.
+        let Ok(target) = repo
+            .revparse_single(name)
+            .and_then(|obj| obj.peel_to_commit())
+        else {
+            continue;
+        };
+        let Some(details) = release::tag_details(repo, name)? else {
+            continue;
+        };
+        transaction.execute(
+            "INSERT INTO tags (name, commit_hash, annotated, tagger_name, tagger_email, date, message)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                name,
+                target.id().to_string(),
+                details.annotated,
+                details.tagger.as_ref().map(|t| t.name.clone()),
+                details.tagger.as_ref().map(|t| t.email.clone()),
+                details.date.to_rfc3339(),
+                details.message,
+            ],
+        )?;
+        count += 1;
+    }
+    Ok(count)
+}
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::testutil::TempRepo;
+    fn rows(connection: &Connection, sql: &str) -> Vec<String> {
+        let mut statement = connection.prepare(sql).unwrap();
+        statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+    #[test]
+    fn reexport_appends_commits_and_rewrites_tags() {
+        let mut repo = TempRepo::new();
+        repo.write("target.txt", "one\ntwo\n");
+        repo.write("link", "placeholder\n");
+        let first = repo
+            .commit("feat(api): add users endpoint\n\nSigned-off-by: Alice <alice@example.com>")
+            .to_string();
+        repo.tag("v1.0.0", "First release");
+        repo.write(".git/info/exclude", "history.db\n");
+        let database = repo.dir.join("history.db");
+
+        let analyzer = GitAnalyzer::new(repo.path()).unwrap();
+        let summary = export_sqlite(&analyzer, &database).unwrap();
+        assert_eq!((summary.added, summary.existing, summary.tags), (1, 0, 1));
+
+        // Replacing a file with a symlink is a delete and an add of one path.
+        std::fs::remove_file(repo.dir.join("link")).unwrap();
+        std::os::unix::fs::symlink("target.txt", repo.dir.join("link")).unwrap();
+        let second = repo.commit("refactor: link to the target").to_string();
+        repo.repo.tag_delete("v1.0.0").unwrap();
+        repo.tag("v1.1.0", "Second release");
+
+        let analyzer = GitAnalyzer::new(repo.path()).unwrap();
+        let summary = export_sqlite(&analyzer, &database).unwrap();
+        assert_eq!((summary.added, summary.existing, summary.tags), (1, 1, 1));
+
+        let connection = Connection::open(&database).unwrap();
+        assert_eq!(
+            rows(&connection, "SELECT hash FROM commits ORDER BY timestamp"),
+            vec![first.clone(), second.clone()]
+        );
+        assert_eq!(
+            rows(
+                &connection,
+                "SELECT key || ': ' || value FROM trailers WHERE commit_hash = (SELECT hash FROM commits ORDER BY timestamp LIMIT 1)"
+            ),
+            vec!["Signed-off-by: Alice <alice@example.com>"]
+        );
+        assert_eq!(
+            rows(
+                &connection,
+                &format!(
+                    "SELECT path || ' +' || insertions || ' -' || deletions FROM files_changed WHERE commit_hash = '{}'",
+                    second
+                )
+            ),
+            vec!["link +1 -1"]
+        );
+        assert_eq!(
+            rows(
+                &connection,
+                "SELECT name || ' ' || commit_hash || ' ' || message FROM tags"
+            ),
+            vec![format!("v1.1.0 {} Second release", second)]
+        );
+    }
+}
@@ -0,0 +1,756 @@
+use crate::core::CommitInfo;
+use crate::error::{RcgenError, Result};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+const ROOT_FIELDS: &[&str] = &[
+    "hash",
+    "short_hash",
+    "author",
+    "date",
+    "message",
+    "summary",
+    "body",
+    "files_changed",
+    "insertions",
+    "deletions",
+    "is_merge",
+    "tags",
+    "branches",
+    "commit_type",
+    "type",
+    "scope",
+    "trailers",
+    "breaking",
+    "notes",
+];
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    Not,
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+    End,
+}
+#[derive(Debug, Clone, Copy)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    StartsWith,
+    EndsWith,
+    Contains,
+    Lower,
+    Upper,
+    Len,
+    IsEmpty,
+}
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    Field(String),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Method, Vec<Expr>),
+    Matches(Box<Expr>, Regex),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CmpOp, Box<Expr>),
+    In(Box<Expr>, Box<Expr>),
+}
+#[derive(Debug, Clone)]
+pub struct FilterExpr {
+    source: String,
+    root: Expr,
+}
+impl FilterExpr {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            pos: 0,
+        };
+        let root = parser.parse_or()?;
+        if parser.peek() != &Token::End {
+            return Err(parser.error("unexpected trailing input"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+    pub fn matches(&self, commit: &CommitInfo) -> Result<bool> {
+        let context = commit_context(commit)?;
+        let value = eval(&self.root, &context).map_err(|e| {
+            RcgenError::FilterExpr(format!("{} (while evaluating `{}`)", e, self.source))
+        })?;
+        Ok(truthy(&value))
+    }
+}
+fn commit_context(commit: &CommitInfo) -> Result<Map<String, Value>> {
+    let mut context = match serde_json::to_value(commit)? {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    let commit_type = context.get("commit_type").cloned().unwrap_or(Value::Null);
+    context.insert("type".to_string(), commit_type);
+    // Trailers are exposed as a map keyed by lower-cased token; repeated
+    // trailers such as `Signed-off-by` are joined with ", ".
+    let mut trailers = Map::new();
+    for trailer in &commit.trailers {
+        let key = trailer.key.to_lowercase();
+        let value = match trailers.get(&key) {
+            Some(Value::String(existing)) => format!("{}, {}", existing, trailer.value),
+            _ => trailer.value.clone(),
+        };
+        trailers.insert(key, Value::String(value));
+    }
+    context.insert("trailers".to_string(), Value::Object(trailers));
+    Ok(context)
+}
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        let token = match two.as_str() {
+            "&&" => Some(Token::And),
+            "||" => Some(Token::Or),
+            "==" => Some(Token::Eq),
+            "!=" => Some(Token::Ne),
+            "<=" => Some(Token::Le),
+            ">=" => Some(Token::Ge),
+            "=~" => Some(Token::Match),
+            _ => None,
+        };
+        if let Some(token) = token {
+            tokens.push((token, column));
+            i += 2;
+            continue;
+        }
+        let token = match c {
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            '[' => Some(Token::LBracket),
+            ']' => Some(Token::RBracket),
+            ',' => Some(Token::Comma),
+            '.' => Some(Token::Dot),
+            '!' => Some(Token::Not),
+            '<' => Some(Token::Lt),
+            '>' => Some(Token::Gt),
+            _ => None,
+        };
+        if let Some(token) = token {
+            tokens.push((token, column));
+            i += 1;
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(RcgenError::FilterExpr(format!(
+                            "unterminated string starting at column {} in `{}`",
+                            column, source
+                        )));
+                    }
+                    Some(&ch) if ch == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some('\\') => {
+                        match chars.get(i + 1) {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(&other) => value.push(other),
+                            None => {
+                                i += 1;
+                                continue;
+                            }
+                        }
+                        i += 2;
+                    }
+                    Some(&ch) => {
+                        value.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push((Token::Str(value), column));
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                // Stop before a method call on a number literal, e.g. `1.len()`.
+                if chars[i] == '.' && !chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()) {
+                    break;
+                }
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text.parse::<f64>().map_err(|_| {
+                RcgenError::FilterExpr(format!(
+                    "invalid number '{}' at column {} in `{}`",
+                    text, column, source
+                ))
+            })?;
+            tokens.push((Token::Num(number), column));
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), column));
+            continue;
+        }
+        return Err(RcgenError::FilterExpr(format!(
+            "unexpected character '{}' at column {} in `{}`",
+            c, column, source
+        )));
+    }
+    tokens.push((Token::End, chars.len() + 1));
+    Ok(tokens)
+}
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+    fn error(&self, message: &str) -> RcgenError {
+        let (token, column) = &self.tokens[self.pos];
+        let found = match token {
+            Token::End => "end of input".to_string(),
+            Token::Ident(name) => format!("'{}'", name),
+            Token::Str(s) => format!("\"{}\"", s),
+            Token::Num(n) => n.to_string(),
+            other => format!("{:?}", other),
+        };
+        RcgenError::FilterExpr(format!(
+            "{}, found {} at column {} in `{}`",
+            message, found, column, self.source
+        ))
+    }
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        if self.peek() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", what)))
+        }
+    }
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.peek() == &Token::Or {
+            self.advance();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == &Token::And {
+            self.advance();
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.peek() == &Token::Not {
+            self.advance();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_comparison()
+    }
+    fn parse_comparison(&mut self) -> Result<Expr> {
+        let left = self.parse_postfix()?;
+        let op = match self.peek() {
+            Token::Eq => CmpOp::Eq,
+            Token::Ne => CmpOp::Ne,
+            Token::Lt => CmpOp::Lt,
+            Token::Le => CmpOp::Le,
+            Token::Gt => CmpOp::Gt,
+            Token::Ge => CmpOp::Ge,
+            Token::Match => {
+                self.advance();
+                let regex = self.parse_regex_literal()?;
+                return Ok(Expr::Matches(Box::new(left), regex));
+            }
+            Token::Ident(word) if word == "in" => {
+                self.advance();
+                let right = self.parse_postfix()?;
+                return Ok(Expr::In(Box::new(left), Box::new(right)));
+            }
+            _ => return Ok(left),
+        };
+        self.advance();
+        let right = self.parse_postfix()?;
+        Ok(Expr::Compare(Box::new(left), op, Box::new(right)))
+    }
+    fn parse_regex_literal(&mut self) -> Result<Regex> {
+        match self.peek().clone() {
+            Token::Str(pattern) => {
+                let regex = Regex::new(&pattern).map_err(|e| {
+                    RcgenError::FilterExpr(format!(
+                        "invalid regex \"{}\" in `{}`: {}",
+                        pattern, self.source, e
+                    ))
+                })?;
+                self.advance();
+                Ok(regex)
+            }
+            _ => Err(self.error("expected a string literal regex")),
+        }
+    }
+    fn parse_postfix(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            match self.peek() {
+                Token::Dot => {
+                    self.advance();
+                    let name_pos = self.pos;
+                    let Token::Ident(name) = self.peek().clone() else {
+                        return Err(self.error("expected a field or method name after '.'"));
+                    };
+                    self.advance();
+                    if self.peek() != &Token::LParen {
+                        expr = Expr::Member(Box::new(expr), name);
+                        continue;
+                    }
+                    self.advance();
+                    if name == "matches" {
+                        let regex = self.parse_regex_literal()?;
+                        self.expect(Token::RParen, "')'")?;
+                        expr = Expr::Matches(Box::new(expr), regex);
+                        continue;
+                    }
+                    let (method, arity) = match name.as_str() {
+                        "starts_with" => (Method::StartsWith, 1),
+                        "ends_with" => (Method::EndsWith, 1),
+                        "contains" => (Method::Contains, 1),
+                        "lower" => (Method::Lower, 0),
+                        "upper" => (Method::Upper, 0),
+                        "len" => (Method::Len, 0),
+                        "is_empty" => (Method::IsEmpty, 0),
+                        _ => {
+                            self.pos = name_pos;
+                            return Err(self.error(&format!("unknown method '{}'", name)));
+                        }
+                    };
+                    let mut args = Vec::new();
+                    if self.peek() != &Token::RParen {
+                        args.push(self.parse_or()?);
+                        while self.peek() == &Token::Comma {
+                            self.advance();
+                            args.push(self.parse_or()?);
+                        }
+                    }
+                    if args.len() != arity {
+                        return Err(self.error(&format!(
+                            "method '{}' takes {} argument(s), got {}",
+                            name,
+                            arity,
+                            args.len()
+                        )));
+                    }
+                    self.expect(Token::RParen, "')'")?;
+                    expr = Expr::Call(Box::new(expr), method, args);
+                }
+                Token::LBracket => {
+                    self.advance();
+                    let index = self.parse_or()?;
+                    self.expect(Token::RBracket, "']'")?;
+                    expr = Expr::Index(Box::new(expr), Box::new(index));
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+    fn parse_primary(&mut self) -> Result<Expr> {
+        match self.peek().clone() {
+            Token::Str(s) => {
+                self.advance();
+                Ok(Expr::Literal(Value::String(s)))
+            }
+            Token::Num(n) => {
+                self.advance();
+                Ok(Expr::Literal(number(n)))
+            }
+            Token::LParen => {
+                self.advance();
+                let expr = self.parse_or()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(expr)
+            }
+            Token::LBracket => {
+                self.advance();
+                let mut items = Vec::new();
+                if self.peek() != &Token::RBracket {
+                    items.push(self.parse_or()?);
+                    while self.peek() == &Token::Comma {
+                        self.advance();
+                        items.push(self.parse_or()?);
+                    }
+                }
+                self.expect(Token::RBracket, "']'")?;
+                Ok(Expr::List(items))
+            }
+            Token::Ident(name) => match name.as_str() {
+                "true" => {
+                    self.advance();
+                    Ok(Expr::Literal(Value::Bool(true)))
+                }
+                "false" => {
+                    self.advance();
+                    Ok(Expr::Literal(Value::Bool(false)))
+                }
+                "null" => {
+                    self.advance();
+                    Ok(Expr::Literal(Value::Null))
+                }
+                _ if ROOT_FIELDS.contains(&name.as_str()) => {
+                    self.advance();
+                    Ok(Expr::Field(name))
+                }
+                _ => Err(self.error(&format!(
+                    "unknown field '{}' (expected one of: {})",
+                    name,
+                    ROOT_FIELDS.join(", ")
+                ))),
+            },
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+fn number(n: f64) -> Value {
+    serde_json::Number::from_f64(n)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "list",
+        Value::Object(_) => "object",
+    }
+}
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+fn contains(haystack: &Value, needle: &Value) -> std::result::Result<bool, String> {
+    match (haystack, needle) {
+        (Value::Null, _) => Ok(false),
+        (Value::String(s), Value::String(n)) => Ok(s.contains(n.as_str())),
+        (Value::Array(items), _) => Ok(items.iter().any(|item| values_equal(item, needle))),
+        (Value::Object(map), Value::String(key)) => Ok(map.contains_key(key)),
+        _ => Err(format!(
+            "cannot check whether {} contains {}",
+            type_name(haystack),
+            type_name(needle)
+        )),
+    }
+}
+fn eval(expr: &Expr, context: &Map<String, Value>) -> std::result::Result<Value, String> {
+    Ok(match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::List(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| eval(item, context))
+                .collect::<std::result::Result<_, _>>()?,
+        ),
+        Expr::Field(name) => context.get(name).cloned().unwrap_or(Value::Null),
+        Expr::Member(target, name) => match eval(target, context)? {
+            Value::Object(map) => map.get(name).cloned().unwrap_or(Value::Null),
+            Value::Null => Value::Null,
+            other => return Err(format!("{} has no field '{}'", type_name(&other), name)),
+        },
+        Expr::Index(target, index) => match (eval(target, context)?, eval(index, context)?) {
+            (Value::Object(map), Value::String(key)) => {
+                map.get(&key.to_lowercase()).cloned().unwrap_or(Value::Null)
+            }
+            (Value::Array(items), Value::Number(n)) => n
+                .as_f64()
+                .and_then(|n| items.get(n as usize).cloned())
+                .unwrap_or(Value::Null),
+            (Value::Null, _) => Value::Null,
+            (target, index) => {
+                return Err(format!(
+                    "cannot index {} with {}",
+                    type_name(&target),
+                    type_name(&index)
+                ));
+            }
+        },
+        Expr::Call(target, method, args) => {
+            let target = eval(target, context)?;
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, context))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            match (method, &target) {
+                (Method::StartsWith, Value::String(s)) | (Method::EndsWith, Value::String(s)) => {
+                    let Value::String(arg) = &args[0] else {
+                        return Err(format!("expected a string, got {}", type_name(&args[0])));
+                    };
+                    Value::Bool(if matches!(method, Method::StartsWith) {
+                        s.starts_with(arg.as_str())
+                    } else {
+                        s.ends_with(arg.as_str())
+                    })
+                }
+                (Method::StartsWith | Method::EndsWith, Value::Null) => Value::Bool(false),
+                (Method::Contains, _) => Value::Bool(contains(&target, &args[0])?),
+                (Method::Lower, Value::String(s)) => Value::String(s.to_lowercase()),
+                (Method::Upper, Value::String(s)) => Value::String(s.to_uppercase()),
+                (Method::Lower | Method::Upper, Value::Null) => Value::Null,
+                (Method::Len | Method::IsEmpty, _) => {
+                    let len = match &target {
+                        Value::Null => 0,
+                        Value::String(s) => s.chars().count(),
+                        Value::Array(items) => items.len(),
+                        Value::Object(map) => map.len(),
+                        other => return Err(format!("{} has no length", type_name(other))),
+                    };
+                    if matches!(method, Method::Len) {
+                        number(len as f64)
+                    } else {
+                        Value::Bool(len == 0)
+                    }
+                }
+                (method, other) => {
+                    return Err(format!(
+                        "method {:?} is not supported on {}",
+                        method,
+                        type_name(other)
+                    ));
+                }
+            }
+        }
+        Expr::Matches(target, regex) => match eval(target, context)? {
+            Value::String(s) => Value::Bool(regex.is_match(&s)),
+            Value::Null => Value::Bool(false),
+            other => {
+                return Err(format!(
+                    "cannot match a regex against {}",
+                    type_name(&other)
+                ));
+            }
+        },
+        Expr::Not(inner) => Value::Bool(!truthy(&eval(inner, context)?)),
+        Expr::And(left, right) => {
+            Value::Bool(truthy(&eval(left, context)?) && truthy(&eval(right, context)?))
+        }
+        Expr::Or(left, right) => {
+            Value::Bool(truthy(&eval(left, context)?) || truthy(&eval(right, context)?))
+        }
+        Expr::In(needle, haystack) => {
+            let needle = eval(needle, context)?;
+            Value::Bool(contains(&eval(haystack, context)?, &needle)?)
+        }
+        Expr::Compare(left, op, right) => {
+            let left = eval(left, context)?;
+            let right = eval(right, context)?;
+            let result = match op {
+                CmpOp::Eq => values_equal(&left, &right),
+                CmpOp::Ne => !values_equal(&left, &right),
+                _ => {
+                    let ordering = match (&left, &right) {
+                        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+                        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                        (Value::Null, _) | (_, Value::Null) => None,
+                        _ => {
+                            return Err(format!(
+                                "cannot compare {} with {}",
+                                type_name(&left),
+                                type_name(&right)
+                            ));
+                        }
+                    };
+                    match ordering {
+                        None => false,
+                        Some(ordering) => match op {
+                            CmpOp::Lt => ordering == Ordering::Less,
+                            CmpOp::Le => ordering != Ordering::Greater,
+                            CmpOp::Gt => ordering == Ordering::Greater,
+                            CmpOp::Ge => ordering != Ordering::Less,
+                            CmpOp::Eq | CmpOp::Ne => unreachable!(),
+                        },
+                    }
+                }
+            };
+            Value::Bool(result)
+        }
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    fn commit() -> CommitInfo {
+        serde_json::from_value(json!({
+            "hash": "0123456789abcdef0123456789abcdef01234567",
+            "short_hash": "01234567",
+            "author": {"name": "Alice", "email": "alice@example.com", "commits_count": 1},
+            "date": "2024-02-15T10:00:00+00:00",
+            "message": "feat(api): add users endpoint\n\nReviewed-by: Bob",
+            "summary": "feat(api): add users endpoint",
+            "body": null,
+            "files_changed": ["src/api.rs", "README.md"],
+            "insertions": 120,
+            "deletions": 4,
+            "is_merge": false,
+            "tags": ["v1.0.0"],
+            "branches": [],
+            "commit_type": "feat",
+            "scope": "api",
+            "trailers": [
+                {"key": "Reviewed-by", "value": "Bob"},
+                {"key": "Signed-off-by", "value": "Alice"},
+                {"key": "Signed-off-by", "value": "Carol"}
+            ]
+        }))
+        .unwrap()
+    }
+    fn eval_str(source: &str) -> bool {
+        FilterExpr::parse(source)
+            .unwrap()
+            .matches(&commit())
+            .unwrap()
+    }
+    fn parse_error(source: &str) -> String {
+        match FilterExpr::parse(source) {
+            Err(RcgenError::FilterExpr(message)) => message,
+            other => panic!("expected a parse error for `{}`, got {:?}", source, other),
+        }
+    }
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert!(eval_str(
+            "type == 'fix' && scope == 'db' || insertions > 100"
+        ));
+        assert!(!eval_str(
+            "type == 'fix' && (scope == 'api' || insertions > 100)"
+        ));
+        assert!(eval_str("true || false && false"));
+        assert!(!eval_str("(true || false) && false"));
+    }
+    #[test]
+    fn not_applies_to_the_nearest_operand() {
+        assert!(!eval_str("!is_merge && is_merge"));
+        assert!(eval_str("!(is_merge && true)"));
+        assert!(eval_str("!!true"));
+    }
+    #[test]
+    fn comparisons_and_membership() {
+        assert!(eval_str("insertions >= 120 && deletions < 5"));
+        assert!(eval_str("type in ['feat', 'fix']"));
+        assert!(eval_str("'v1.0.0' in tags"));
+        assert!(eval_str("summary =~ '^feat\\\\('"));
+        assert!(eval_str("files_changed.len() == 2"));
+        assert!(eval_str("author.name.lower() == 'alice'"));
+    }
+    #[test]
+    fn trailers_are_case_insensitive_and_joined() {
+        assert!(eval_str("trailers['reviewed-by'] == 'Bob'"));
+        assert!(eval_str("trailers['Signed-off-by'] == 'Alice, Carol'"));
+        assert!(eval_str("trailers['acked-by'] == null"));
+    }
+    #[test]
+    fn breaking_and_notes_fields() {
+        let mut commit = commit();
+        commit.breaking = true;
+        commit.notes = vec!["Rotate TLS certificates".to_string()];
+        let filter = FilterExpr::parse("breaking && notes.len() == 1").unwrap();
+        assert!(filter.matches(&commit).unwrap());
+        let filter = FilterExpr::parse("'Rotate TLS certificates' in notes").unwrap();
+        assert!(filter.matches(&commit).unwrap());
+        assert!(!eval_str("breaking || !notes.is_empty()"));
+    }
+    #[test]
+    fn rejects_invalid_tokens() {
+        assert!(parse_error("type == 'fix' & scope").contains("unexpected character '&'"));
+        assert!(parse_error("summary == 'open").contains("unterminated string"));
+        assert!(parse_error("type ==").contains("expected a value"));
+        assert!(parse_error("type == 'fix')").contains("unexpected trailing input"));
+    }
+    #[test]
+    fn rejects_unknown_names() {
+        assert!(parse_error("colour == 'red'").contains("unknown field 'colour'"));
+        assert!(parse_error("summary.shout()").contains("unknown method 'shout'"));
+        assert!(parse_error("summary.starts_with()").contains("takes 1 argument(s), got 0"));
+        assert!(parse_error("summary =~ '('").contains("invalid regex"));
+    }
+    #[test]
+    fn type_errors_surface_at_evaluation() {
+        let filter = FilterExpr::parse("insertions < 'many'").unwrap();
+        assert!(filter.matches(&commit()).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::html::escape;
+use chrono::{DateTime, FixedOffset};
+#[derive(Debug, Clone)]
+pub struct Person {
+    pub name: String,
+    pub email: Option<String>,
+}
+impl Person {
+    // Accepts `Name <email>` as well as a bare name.
+    pub fn parse(value: &str) -> Self {
+        match value.split_once('<') {
+            Some((name, rest)) => Self {
+                name: name.trim().to_string(),
+                email: Some(rest.trim_end_matches('>').trim().to_string()),
+            },
+            None => Self {
+                name: value.trim().to_string(),
+                email: None,
+            },
+        }
+    }
+}
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub id: String,
+    pub permalink: bool,
+    pub title: String,
+    pub link: Option<String>,
+    pub date: DateTime<FixedOffset>,
+    pub author: Option<Person>,
+    pub categories: Vec<String>,
+    pub content: String,
+}
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    pub self_link: Option<String>,
+    pub author: Person,
+    pub updated: DateTime<FixedOffset>,
+    pub entries: Vec<Entry>,
+}
+pub fn atom(feed: &Feed) -> String {
+    let mut output = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    output.push_str(&format!("  <id>{}</id>\n", escape(&feed.id)));
+    output.push_str(&format!("  <title>{}</title>\n", escape(&feed.title)));
+    output.push_str(&format!(
+        "  <subtitle>{}</subtitle>\n",
+        escape(&feed.description)
+    ));
+    output.push_str(&format!(
+        "  <updated>{}</updated>\n",
+        feed.updated.to_rfc3339()
+    ));
+    if let Some(link) = &feed.link {
+        output.push_str(&format!(
+            "  <link rel=\"alternate\" href=\"{}\"/>\n",
+            escape(link)
+        ));
+    }
+    if let Some(link) = &feed.self_link {
+        output.push_str(&format!(
+            "  <link rel=\"self\" href=\"{}\"/>\n",
+            escape(link)
+        ));
+    }
+    output.push_str(&atom_person("author", &feed.author, "  "));
+    output.push_str("  <generator>rcgen</generator>\n");
+
+    for entry in &feed.entries {
+        output.push_str("  <entry>\n");
+        output.push_str(&format!("    <id>{}</id>\n", escape(&entry.id)));
+        output.push_str(&format!("    <title>{}</title>\n", escape(&entry.title)));
+        output.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.date.to_rfc3339()
+        ));
+        output.push_str(&format!(
+            "    <published>{}</published>\n",
+            entry.date.to_rfc3339()
+        ));
+        if let Some(link) = &entry.link {
+            output.push_str(&format!(
+                "    <link rel=\"alternate\" href=\"{}\"/>\n",
+                escape(link)
+            ));
+        }
+        if let Some(author) = &entry.author {
+            output.push_str(&atom_person("author", author, "    "));
+        }
+        for category in &entry.categories {
+            output.push_str(&format!("    <category term=\"{}\"/>\n", escape(category)));
+        }
+        output.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape(&entry.content)
+        ));
+        output.push_str("  </entry>\n");
+    }
+
+    output.push_str("</feed>\n");
+    output
+}
+fn atom_person(element: &str, person: &Person, indent: &str) -> String {
+    let mut output = format!(
+        "{}<{}>\n{}  <name>{}</name>\n",
+        indent,
+        element,
+        indent,
+        escape(&person.name)
+    );
+    if let Some(email) = &person.email {
+        output.push_str(&format!("{}  <email>{}</email>\n", indent, escape(email)));
+    }
+    output.push_str(&format!("{}</{}>\n", indent, element));
+    output
+}
+pub fn rss(feed: &Feed) -> String {
+    let mut output = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n",
+    );
+    output.push_str(&format!("  <title>{}</title>\n", escape(&feed.title)));
+    // RSS requires a channel link; the feed id is the best stand-in when the
+    // repository has no web URL.
+    output.push_str(&format!(
+        "  <link>{}</link>\n",
+        escape(feed.link.as_deref().unwrap_or(&feed.id))
+    ));
+    output.push_str(&format!(
+        "  <description>{}</description>\n",
+        escape(&feed.description)
+    ));
+    if let Some(link) = &feed.self_link {
+        output.push_str(&format!(
+            "  <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+            escape(link)
+        ));
+    }
+    if let Some(email) = &feed.author.email {
+        output.push_str(&format!(
+            "  <managingEditor>{} ({})</managingEditor>\n",
+            escape(email),
+            escape(&feed.author.name)
+        ));
+    }
+    output.push_str(&format!(
+        "  <lastBuildDate>{}</lastBuildDate>\n",
+        feed.updated.to_rfc2822()
+    ));
+    output.push_str("  <generator>rcgen</generator>\n");
+
+    for entry in &feed.entries {
+        output.push_str("  <item>\n");
+        output.push_str(&format!("    <title>{}</title>\n", escape(&entry.title)));
+        if let Some(link) = &entry.link {
+            output.push_str(&format!("    <link>{}</link>\n", escape(link)));
+        }
+        output.push_str(&format!(
+            "    <guid isPermaLink=\"{}\">{}</guid>\n",
+            entry.permalink,
+            escape(&entry.id)
+        ));
+        output.push_str(&format!(
+            "    <pubDate>{}</pubDate>\n",
+            entry.date.to_rfc2822()
+        ));
+        // RSS only allows an email address here, so authors without one are
+        // left out.
+        if let Some(author) = &entry.author
+            && let Some(email) = &author.email
+        {
+            output.push_str(&format!(
+                "    <author>{} ({})</author>\n",
+                escape(email),
+                escape(&author.name)
+            ));
+        }
+        for category in &entry.categories {
+            output.push_str(&format!("    <category>{}</category>\n", escape(category)));
+        }
+        output.push_str(&format!(
+            "    <description>{}</description>\n",
+            escape(&entry.content)
+        ));
+        output.push_str("  </item>\n");
+    }
+
+    output.push_str("</channel>\n</rss>\n");
+    output
+}
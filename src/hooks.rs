@@ -0,0 +1,161 @@
+use crate::error::{RcgenError, Result};
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+pub const MANAGED_MARKER: &str = "# rcgen-managed hook";
+pub const CHAINED_SUFFIX: &str = ".rcgen-chained";
+pub const MANAGED_HOOKS: &[&str] = &["commit-msg", "post-commit", "reference-transaction"];
+pub struct HookOutcome {
+    pub output: String,
+    pub success: bool,
+}
+pub fn hooks_dir(repo: &Repository) -> Result<PathBuf> {
+    if let Ok(path) = repo.config()?.get_path("core.hooksPath") {
+        if path.is_absolute() {
+            return Ok(path);
+        }
+        // Relative hook paths are resolved against the work tree, like git does.
+        let base = repo.workdir().unwrap_or_else(|| repo.path());
+        return Ok(base.join(path));
+    }
+    Ok(repo.path().join("hooks"))
+}
+pub fn install(repo: &Repository, hooks: &[&str]) -> Result<Vec<String>> {
+    let dir = hooks_dir(repo)?;
+    fs::create_dir_all(&dir)?;
+    let rcgen = std::env::current_exe()
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "rcgen".to_string());
+    let mut messages = Vec::new();
+    for &name in hooks {
+        let path = dir.join(name);
+        let chained = chained_path(&path);
+        if path.exists() && !is_managed(&path)? {
+            if chained.exists() {
+                return Err(RcgenError::Config(format!(
+                    "Cannot install {}: both {} and {} already exist",
+                    name,
+                    path.display(),
+                    chained.display()
+                )));
+            }
+            fs::rename(&path, &chained)?;
+            messages.push(format!(
+                "Existing {} hook moved to {} and chained",
+                name,
+                chained.display()
+            ));
+        }
+        fs::write(&path, hook_script(name, &rcgen))?;
+        make_executable(&path)?;
+        messages.push(format!("Installed {} hook at {}", name, path.display()));
+    }
+    Ok(messages)
+}
+pub fn uninstall(repo: &Repository) -> Result<Vec<String>> {
+    let dir = hooks_dir(repo)?;
+    let mut messages = Vec::new();
+    for name in MANAGED_HOOKS {
+        let path = dir.join(name);
+        if !path.exists() || !is_managed(&path)? {
+            continue;
+        }
+        fs::remove_file(&path)?;
+        messages.push(format!("Removed {} hook", name));
+        let chained = chained_path(&path);
+        if chained.exists() {
+            fs::rename(&chained, &path)?;
+            messages.push(format!("Restored previous {} hook", name));
+        }
+    }
+    Ok(messages)
+}
+pub fn created_tags(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let old = parts.next()?;
+            let _new = parts.next()?;
+            let reference = parts.next()?;
+            let tag = reference.strip_prefix("refs/tags/")?;
+            // A zero old value means the ref did not exist before.
+            old.chars().all(|c| c == '0').then(|| tag.to_string())
+        })
+        .collect()
+}
+pub fn head_tags(repo: &Repository) -> Result<Vec<String>> {
+    let head = repo.head()?.peel_to_commit()?.id();
+    let mut tags = Vec::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let target = repo
+            .revparse_single(name)
+            .and_then(|obj| obj.peel_to_commit());
+        if target.is_ok_and(|commit| commit.id() == head) {
+            tags.push(name.to_string());
+        }
+    }
+    Ok(tags)
+}
+fn chained_path(path: &Path) -> PathBuf {
+    let mut chained = path.as_os_str().to_owned();
+    chained.push(CHAINED_SUFFIX);
+    PathBuf::from(chained)
+}
+fn is_managed(path: &Path) -> Result<bool> {
+    let content = fs::read(path)?;
+    Ok(String::from_utf8_lossy(&content).contains(MANAGED_MARKER))
+}
+fn hook_script(name: &str, rcgen: &str) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n{}: remove with `rcgen hooks uninstall`\nhook_dir=$(dirname \"$0\")\nchained=\"$hook_dir/{}{}\"\n",
+        MANAGED_MARKER, name, CHAINED_SUFFIX
+    );
+    // reference-transaction receives the updated refs on stdin, which both the
+    // chained hook and rcgen need to see.
+    let reads_stdin = name == "reference-transaction";
+    if reads_stdin {
+        script.push_str("input=$(cat)\n");
+        script.push_str("if [ -x \"$chained\" ]; then\n    printf '%s\\n' \"$input\" | \"$chained\" \"$@\" || exit $?\nfi\n");
+        script.push_str("[ \"$1\" = committed ] || exit 0\n");
+    } else {
+        script.push_str("if [ -x \"$chained\" ]; then\n    \"$chained\" \"$@\" || exit $?\nfi\n");
+    }
+    script.push_str(&format!(
+        "rcgen=\"${{RCGEN:-{}}}\"\ncommand -v \"$rcgen\" >/dev/null 2>&1 || rcgen=rcgen\n",
+        shell_escape(rcgen)
+    ));
+    if reads_stdin {
+        script.push_str(&format!(
+            "printf '%s\\n' \"$input\" | exec \"$rcgen\" hooks run {} \"$@\"\n",
+            name
+        ));
+    } else {
+        script.push_str(&format!("exec \"$rcgen\" hooks run {} \"$@\"\n", name));
+    }
+    script
+}
+// Escapes the characters that stay special inside a double-quoted string.
+fn shell_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '"' | '$' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
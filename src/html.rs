@@ -0,0 +1,342 @@
+use crate::cli::Theme;
+use crate::core::{AuthorStats, CommitInfo, GitAnalyzer, RepositoryStats};
+use crate::release::{self, ReleaseSection};
+const STYLE: &str = r#"
+*{box-sizing:border-box}
+body{margin:0;font:15px/1.5 -apple-system,BlinkMacSystemFont,"Segoe UI",Helvetica,Arial,sans-serif;background:var(--bg);color:var(--fg)}
+main{max-width:960px;margin:0 auto;padding:2rem 1.5rem}
+a{color:var(--link)}
+h1{margin-top:0}
+h2{border-bottom:1px solid var(--border);padding-bottom:.3rem}
+h2 small,.meta,.date{color:var(--muted);font-weight:normal}
+nav ul{padding-left:1.2rem}
+details{margin:.5rem 0;border:1px solid var(--border);border-radius:6px;background:var(--panel)}
+summary{cursor:pointer;padding:.5rem .8rem;font-weight:600}
+.count{display:inline-block;min-width:1.6rem;margin-left:.4rem;padding:0 .4rem;border-radius:1rem;background:var(--border);font-size:.8rem;text-align:center}
+ul.commits{list-style:none;margin:0;padding:0 .8rem .5rem}
+ul.commits li{display:flex;gap:.7rem;padding:.4rem 0;border-top:1px solid var(--border)}
+.avatar{flex:none;width:2rem;height:2rem;border-radius:50%;color:#fff;font-size:.75rem;font-weight:600;line-height:2rem;text-align:center}
+.summary{margin:0}
+.meta{margin:0;font-size:.85rem}
+.badge{display:inline-block;margin-right:.3rem;padding:0 .4rem;border-radius:4px;background:var(--danger);color:#fff;font-size:.75rem;font-weight:600}
+blockquote{margin:.3rem 0;padding-left:.7rem;border-left:3px solid var(--border);color:var(--muted);white-space:pre-wrap}
+code{font-family:ui-monospace,SFMono-Regular,Menlo,Consolas,monospace;font-size:.85em}
+table{border-collapse:collapse;width:100%;margin:.5rem 0 1.5rem}
+th,td{padding:.35rem .6rem;border-bottom:1px solid var(--border);text-align:left}
+td.num,th.num{text-align:right}
+footer{margin-top:2rem;color:var(--muted);font-size:.8rem}
+"#;
+const LIGHT: &str = "--bg:#ffffff;--fg:#1f2328;--muted:#656d76;--panel:#f6f8fa;--border:#d0d7de;--link:#0969da;--danger:#cf222e";
+const DARK: &str = "--bg:#0d1117;--fg:#e6edf3;--muted:#8d96a0;--panel:#161b22;--border:#30363d;--link:#4493f8;--danger:#da3633";
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+pub fn page(title: &str, theme: Theme, body: &str) -> String {
+    let colors = match theme {
+        Theme::Light => LIGHT,
+        Theme::Dark => DARK,
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>:root{{{}}}{}</style>\n</head>\n<body>\n<main>\n<h1>{}</h1>\n{}<footer>Generated by rcgen on {}</footer>\n</main>\n</body>\n</html>\n",
+        escape(title),
+        colors,
+        STYLE,
+        escape(title),
+        body,
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    )
+}
+pub fn render_changelog(
+    analyzer: &GitAnalyzer,
+    sections: &[ReleaseSection],
+    base_url: Option<&str>,
+    date_format: &str,
+) -> String {
+    let sections: Vec<&ReleaseSection> = sections
+        .iter()
+        .filter(|s| s.tag.is_some() || !s.commits.is_empty())
+        .collect();
+    if sections.is_empty() {
+        return "<p>No commits found</p>\n".to_string();
+    }
+
+    let mut output = String::new();
+    if sections.len() > 1 {
+        output.push_str("<nav>\n<h2>Releases</h2>\n<ul>\n");
+        for section in &sections {
+            output.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a>",
+                release::anchor(&section.label),
+                escape(&section.label)
+            ));
+            if let Some(date) = section.date {
+                output.push_str(&format!(
+                    " <span class=\"date\">{}</span>",
+                    escape(&date.format(date_format).to_string())
+                ));
+            }
+            output.push_str(&format!(" ({})</li>\n", section.commits.len()));
+        }
+        output.push_str("</ul>\n</nav>\n");
+    }
+
+    for section in sections {
+        output.push_str(&format!(
+            "<section id=\"{}\">\n<h2>{}",
+            release::anchor(&section.label),
+            escape(&section.label)
+        ));
+        if let Some(date) = section.date {
+            output.push_str(&format!(
+                " <small>{}</small>",
+                escape(&date.format(date_format).to_string())
+            ));
+        }
+        output.push_str("</h2>\n");
+
+        if let (Some(base), Some(previous)) = (base_url, &section.previous) {
+            let target = section.tag.as_deref().unwrap_or("HEAD");
+            output.push_str(&format!(
+                "<p class=\"meta\"><a href=\"{}\">Compare {}...{}</a></p>\n",
+                escape(&release::compare_link(base, previous, target)),
+                escape(previous),
+                escape(target)
+            ));
+        }
+
+        for group in analyzer.group_commits(&section.commits) {
+            output.push_str(&format!(
+                "<details open>\n<summary>{}<span class=\"count\">{}</span></summary>\n",
+                escape(&group.name),
+                group.commits.len()
+            ));
+            if let Some(description) = &group.description {
+                output.push_str(&format!("<p class=\"meta\">{}</p>\n", escape(description)));
+            }
+            output.push_str("<ul class=\"commits\">\n");
+            for commit in &group.commits {
+                output.push_str(&render_commit(commit, base_url, date_format));
+            }
+            output.push_str("</ul>\n</details>\n");
+        }
+        if !section.new_contributors.is_empty() {
+            output.push_str(&format!(
+                "<details open>\n<summary>New Contributors<span class=\"count\">{}</span></summary>\n<ul>\n",
+                section.new_contributors.len()
+            ));
+            for contributor in &section.new_contributors {
+                let hash = format!("<code>{}</code>", escape(&contributor.short_hash));
+                let commit = match &contributor.url {
+                    Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), hash),
+                    None => hash,
+                };
+                output.push_str(&format!(
+                    "<li>{} made their first contribution in {}</li>\n",
+                    escape(&contributor.author.name),
+                    commit
+                ));
+            }
+            output.push_str("</ul>\n</details>\n");
+        }
+        output.push_str("</section>\n");
+    }
+
+    output
+}
+fn render_commit(commit: &CommitInfo, base_url: Option<&str>, date_format: &str) -> String {
+    let mut output = format!(
+        "<li>{}<div>\n<p class=\"summary\">",
+        avatar(&commit.author.name, &commit.author.email)
+    );
+    if commit.breaking {
+        output.push_str("<span class=\"badge\">BREAKING</span>");
+    }
+    output.push_str(&escape(&commit.summary));
+    output.push_str("</p>\n<p class=\"meta\">");
+
+    output.push_str(&commit_reference(commit, base_url));
+    output.push_str(&format!(
+        " · {} · {}",
+        escape(&commit.author.name),
+        escape(&commit.date.format(date_format).to_string())
+    ));
+    if !commit.tags.is_empty() {
+        output.push_str(&format!(" · tags: {}", escape(&commit.tags.join(", "))));
+    }
+    if !commit.files_changed.is_empty() {
+        output.push_str(&format!(
+            " · {} files changed (+{} -{})",
+            commit.files_changed.len(),
+            commit.insertions,
+            commit.deletions
+        ));
+    }
+    output.push_str("</p>\n");
+
+    for note in &commit.notes {
+        output.push_str(&format!(
+            "<blockquote><strong>Note:</strong> {}</blockquote>\n",
+            escape(note)
+        ));
+    }
+    if let Some(body) = &commit.body
+        && !body.trim().is_empty()
+    {
+        output.push_str(&format!(
+            "<blockquote>{}</blockquote>\n",
+            escape(body.trim())
+        ));
+    }
+
+    output.push_str("</div></li>\n");
+    output
+}
+pub fn commit_reference(commit: &CommitInfo, base_url: Option<&str>) -> String {
+    let hash = format!("<code>{}</code>", escape(&commit.short_hash));
+    match base_url {
+        Some(base) => format!(
+            "<a href=\"{}\">{}</a>",
+            escape(&release::commit_link(base, &commit.hash)),
+            hash
+        ),
+        None => hash,
+    }
+}
+pub fn render_stats(stats: &RepositoryStats, detailed: bool) -> String {
+    let mut output = String::from("<section id=\"statistics\">\n<h2>Statistics</h2>\n<table>\n");
+    let mut row = |label: &str, value: String| {
+        output.push_str(&format!(
+            "<tr><th>{}</th><td>{}</td></tr>\n",
+            label,
+            escape(&value)
+        ));
+    };
+    row("Total commits", stats.total_commits.to_string());
+    row("Total authors", stats.total_authors.to_string());
+    row("Files changed", stats.files_changed.to_string());
+    row(
+        "Total changes",
+        format!("+{} / -{}", stats.total_insertions, stats.total_deletions),
+    );
+    row("Bus factor", format!("{:.1}", stats.bus_factor));
+    if let Some(first) = stats.first_commit
+        && let Some(last) = stats.last_commit
+    {
+        row(
+            "Period",
+            format!(
+                "{} to {} ({} days)",
+                first.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d"),
+                stats.period_days.unwrap_or(0)
+            ),
+        );
+        row("Commits per day", format!("{:.2}", stats.commits_per_day));
+    }
+    if let Some(day) = &stats.most_active_day {
+        row("Most active day", day.clone());
+    }
+    row(
+        "Most active hour",
+        format!("{:02}:00", stats.most_active_hour),
+    );
+    output.push_str("</table>\n");
+
+    if !stats.authors.is_empty() {
+        let limit = if detailed { stats.authors.len() } else { 10 };
+        output.push_str(&format!(
+            "<h3>{}</h3>\n<table>\n<tr><th></th><th>Author</th><th class=\"num\">Commits</th><th class=\"num\">Share</th><th class=\"num\">Insertions</th><th class=\"num\">Deletions</th>",
+            if detailed { "Authors" } else { "Top Contributors" }
+        ));
+        if detailed {
+            output.push_str("<th>First commit</th><th>Last commit</th><th>Commit types</th>");
+        }
+        output.push_str("</tr>\n");
+        for author in stats.authors.iter().take(limit) {
+            output.push_str(&render_author_row(author, stats.total_commits, detailed));
+        }
+        output.push_str("</table>\n");
+    }
+
+    if detailed && !stats.commit_types.is_empty() {
+        let mut types: Vec<(&String, &usize)> = stats.commit_types.iter().collect();
+        types.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        output.push_str("<h3>Commit Types</h3>\n<table>\n<tr><th>Type</th><th class=\"num\">Commits</th><th class=\"num\">Share</th></tr>\n");
+        for (commit_type, count) in types {
+            output.push_str(&format!(
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td></tr>\n",
+                escape(commit_type),
+                count,
+                percentage(*count, stats.total_commits)
+            ));
+        }
+        output.push_str("</table>\n");
+    }
+
+    output.push_str("</section>\n");
+    output
+}
+fn render_author_row(author: &AuthorStats, total_commits: usize, detailed: bool) -> String {
+    let mut output = format!(
+        "<tr><td>{}</td><td>{} <span class=\"meta\">&lt;{}&gt;</span></td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">+{}</td><td class=\"num\">-{}</td>",
+        avatar(&author.author.name, &author.author.email),
+        escape(&author.author.name),
+        escape(&author.author.email),
+        author.commits,
+        percentage(author.commits, total_commits),
+        author.insertions,
+        author.deletions
+    );
+    if detailed {
+        let mut types: Vec<(&String, &usize)> = author.commit_types.iter().collect();
+        types.sort();
+        let types: Vec<String> = types.iter().map(|(k, v)| format!("{}: {}", k, v)).collect();
+        output.push_str(&format!(
+            "<td>{}</td><td>{}</td><td>{}</td>",
+            author.first_commit.format("%Y-%m-%d"),
+            author.last_commit.format("%Y-%m-%d"),
+            escape(&types.join(", "))
+        ));
+    }
+    output.push_str("</tr>\n");
+    output
+}
+fn avatar(name: &str, email: &str) -> String {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let mut initials: String = match words.as_slice() {
+        [] => String::from("?"),
+        [only] => only.chars().take(1).collect(),
+        [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    initials = initials.to_uppercase();
+    // A stable hue per email keeps an author's colour the same across pages.
+    let hue = email
+        .to_lowercase()
+        .bytes()
+        .fold(0u32, |hash, b| hash.wrapping_mul(31).wrapping_add(b as u32))
+        % 360;
+    format!(
+        "<span class=\"avatar\" style=\"background:hsl({},55%,42%)\" title=\"{}\">{}</span>",
+        hue,
+        escape(&format!("{} <{}>", name, email)),
+        escape(&initials)
+    )
+}
+fn percentage(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f32 / total as f32 * 100.0
+    }
+}
@@ -0,0 +1,233 @@
+use crate::config::{ChangeCategory, KeepAChangelogConfig};
+use crate::conventional;
+use crate::core::CommitInfo;
+use crate::error::Result;
+use crate::utils;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+pub const DEFAULT_PREAMBLE: &str = "# Changelog\n\nAll notable changes to this project will be documented in this file.\n\nThe format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\nand this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n";
+static SECTION_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^## \[?([^\]\s]+)\]?").unwrap());
+static LINK_DEFINITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[([^\]]+)\]:\s*(\S+)\s*$").unwrap());
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub label: String,
+    pub text: String,
+}
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub preamble: String,
+    pub sections: Vec<Section>,
+    pub links: Vec<(String, String)>,
+}
+impl Document {
+    pub fn parse(content: &str) -> Self {
+        let mut document = Document::default();
+        let lines: Vec<&str> = content.lines().collect();
+        // Only the block of link definitions that ends the file is managed;
+        // definitions elsewhere stay in the text they belong to.
+        let links_start = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty() && !LINK_DEFINITION.is_match(line))
+            .map_or(0, |last| last + 1);
+        for line in &lines[links_start..] {
+            if let Some(caps) = LINK_DEFINITION.captures(line) {
+                document
+                    .links
+                    .push((caps[1].to_string(), caps[2].to_string()));
+            }
+        }
+        let mut current: Option<Section> = None;
+        for line in &lines[..links_start] {
+            if let Some(caps) = SECTION_HEADING.captures(line) {
+                document.sections.extend(current.take());
+                current = Some(Section {
+                    label: caps[1].to_string(),
+                    text: format!("{}\n", line),
+                });
+                continue;
+            }
+            match &mut current {
+                Some(section) => {
+                    section.text.push_str(line);
+                    section.text.push('\n');
+                }
+                None => {
+                    document.preamble.push_str(line);
+                    document.preamble.push('\n');
+                }
+            }
+        }
+        document.sections.extend(current);
+        for section in &mut document.sections {
+            section.text = format!("{}\n", section.text.trim_end());
+        }
+        document
+    }
+    pub fn section(&self, label: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.label.eq_ignore_ascii_case(label))
+    }
+    pub fn link(&self, label: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(label))
+            .map(|(_, url)| url.as_str())
+    }
+    pub fn render(&self) -> String {
+        let mut output = format!("{}\n", self.preamble.trim_end());
+        for section in &self.sections {
+            output.push('\n');
+            output.push_str(&section.text);
+        }
+        if !self.links.is_empty() {
+            output.push('\n');
+            for (label, url) in &self.links {
+                output.push_str(&format!("[{}]: {}\n", label, url));
+            }
+        }
+        output
+    }
+}
+struct Rule {
+    category: ChangeCategory,
+    types: Vec<String>,
+    scopes: Vec<String>,
+    pattern: Option<Regex>,
+}
+pub struct Categorizer {
+    rules: Vec<Rule>,
+    fallback: Option<ChangeCategory>,
+}
+impl Categorizer {
+    pub fn new(config: &KeepAChangelogConfig) -> Result<Self> {
+        let mut rules = Vec::new();
+        for rule in &config.rules {
+            let pattern = rule.pattern.as_deref().map(Regex::new).transpose()?;
+            rules.push(Rule {
+                category: rule.category,
+                types: rule.types.iter().map(|t| t.to_lowercase()).collect(),
+                scopes: rule.scopes.clone(),
+                pattern,
+            });
+        }
+        Ok(Self {
+            rules,
+            fallback: config.fallback,
+        })
+    }
+    pub fn categorize(&self, commit: &CommitInfo) -> Option<ChangeCategory> {
+        let commit_type = commit.commit_type.as_deref().map(str::to_lowercase);
+        for Rule {
+            category,
+            types,
+            scopes,
+            pattern,
+        } in &self.rules
+        {
+            if types.is_empty() && scopes.is_empty() && pattern.is_none() {
+                continue;
+            }
+            // Every criterion a rule sets has to match.
+            let type_matches = types.is_empty()
+                || commit_type
+                    .as_ref()
+                    .is_some_and(|t| types.iter().any(|allowed| allowed == t));
+            let scope_matches = scopes.is_empty()
+                || commit
+                    .scope
+                    .as_ref()
+                    .is_some_and(|s| scopes.iter().any(|allowed| allowed == s));
+            let pattern_matches = pattern.as_ref().is_none_or(|p| p.is_match(&commit.summary));
+            if type_matches && scope_matches && pattern_matches {
+                return Some(*category);
+            }
+        }
+        self.fallback
+    }
+}
+pub fn render_section(
+    categorizer: &Categorizer,
+    label: &str,
+    date: Option<&str>,
+    commits: &[CommitInfo],
+) -> String {
+    let mut output = match date {
+        Some(date) => format!("## [{}] - {}\n", label, date),
+        None => format!("## [{}]\n", label),
+    };
+    let mut categories: BTreeMap<ChangeCategory, Vec<&CommitInfo>> = BTreeMap::new();
+    for commit in commits {
+        if let Some(category) = categorizer.categorize(commit) {
+            categories.entry(category).or_default().push(commit);
+        }
+    }
+    for category in ChangeCategory::ALL {
+        let Some(entries) = categories.get(&category) else {
+            continue;
+        };
+        output.push_str(&format!("\n### {}\n\n", category.title()));
+        for commit in entries {
+            output.push_str(&format!("- {}\n", entry_text(commit)));
+        }
+    }
+    output
+}
+fn entry_text(commit: &CommitInfo) -> String {
+    let mut text = utils::capitalize(conventional::description(&commit.summary));
+    if let Some(scope) = &commit.scope {
+        text = format!("**{}:** {}", scope, text);
+    }
+    if commit.breaking {
+        text = format!("**BREAKING:** {}", text);
+    }
+    text
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const CHANGELOG: &str = "# Changelog
+
+## [Unreleased]
+
+## [1.1.0] - 2024-02-15
+
+### Fixed
+
+- Handle empty input ([#12])
+
+[#12]: https://github.com/acme/widget/issues/12
+
+## [1.0.0] - 2024-01-05
+
+### Added
+
+- First release
+
+[unreleased]: https://github.com/acme/widget/compare/v1.1.0...HEAD
+[1.1.0]: https://github.com/acme/widget/compare/v1.0.0...v1.1.0
+";
+    #[test]
+    fn only_trailing_link_definitions_are_managed() {
+        let document = Document::parse(CHANGELOG);
+        assert_eq!(document.sections.len(), 3);
+        assert!(
+            document.sections[1]
+                .text
+                .ends_with("[#12]: https://github.com/acme/widget/issues/12\n")
+        );
+        assert_eq!(document.links.len(), 2);
+        assert_eq!(
+            document.link("Unreleased"),
+            Some("https://github.com/acme/widget/compare/v1.1.0...HEAD")
+        );
+        assert_eq!(document.link("#12"), None);
+    }
+    #[test]
+    fn render_round_trips() {
+        assert_eq!(Document::parse(CHANGELOG).render(), CHANGELOG);
+    }
+}
@@ -1,114 +1,399 @@
+pub mod chat;
 pub mod cli;
 pub mod config;
+pub mod conventional;
 pub mod core;
+pub mod email;
 pub mod error;
+pub mod export;
+pub mod expr;
+pub mod feed;
+pub mod hooks;
+pub mod html;
+pub mod keepachangelog;
+pub mod lint;
+pub mod markup;
+pub mod news;
+pub mod overrides;
+pub mod packaging;
+pub mod plugins;
+pub mod release;
+#[cfg(test)]
+mod testutil;
 pub mod utils;
+pub mod version;
+
+use crate::cli::{
+    Bump, LintFormat, OutputFormat, SortOrder, StatsFormat, TagsFormat, Theme, WhichReleaseFormat,
+};
+use crate::config::Config;
+use crate::core::{CommitFilter, CommitInfo, GitAnalyzer};
+use crate::error::{RcgenError, Result};
+use crate::hooks::HookOutcome;
+use crate::lint::{LintReport, Linter, Severity};
+use crate::plugins::ChangelogDocument;
+use crate::version::Version;
+
+#[derive(Debug, Clone)]
+pub struct GenOptions {
+    pub format: OutputFormat,
+    pub limit: usize,
+    pub author: Option<String>,
+    pub grep: Option<String>,
+    pub filter: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub include_body: bool,
+    pub group_by_type: bool,
+    pub sort_order: SortOrder,
+    pub exclude_merges: bool,
+    pub include_stats: bool,
+    pub include_diff_stats: bool,
+    pub release_mode: bool,
+    pub formatter: Option<String>,
+    pub theme: Theme,
+}
 
-use crate::cli::{OutputFormat, SortOrder};
-use crate::core::{CommitInfo, GitAnalyzer};
-use crate::error::Result;
+impl Default for GenOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Md,
+            limit: 0,
+            author: None,
+            grep: None,
+            filter: None,
+            since: None,
+            until: None,
+            include_body: false,
+            group_by_type: false,
+            sort_order: SortOrder::Reverse,
+            exclude_merges: false,
+            include_stats: false,
+            include_diff_stats: false,
+            release_mode: false,
+            formatter: None,
+            theme: Theme::Light,
+        }
+    }
+}
+
+impl GenOptions {
+    fn commit_filter<'a>(&'a self, range: Option<&'a str>) -> CommitFilter<'a> {
+        CommitFilter {
+            range,
+            limit: self.limit,
+            author: self.author.as_deref(),
+            grep: self.grep.as_deref(),
+            expression: self.filter.as_deref(),
+            since: self.since.as_deref(),
+            until: self.until.as_deref(),
+            include_body: self.include_body,
+            sort_order: self.sort_order,
+            exclude_merges: self.exclude_merges,
+            include_diff_stats: self.include_diff_stats,
+        }
+    }
+}
+
+// Returns the changelog along with warnings about the overrides file.
+pub fn generate_changelog(repo_path: &str, options: &GenOptions) -> Result<(String, Vec<String>)> {
+    if options.format == OutputFormat::Ndjson && options.formatter.is_none() {
+        let mut buffer = Vec::new();
+        let warnings = write_ndjson(repo_path, &mut buffer, options)?;
+        return Ok((String::from_utf8_lossy(&buffer).into_owned(), warnings));
+    }
 
-pub fn generate_changelog(
-    repo_path: &str,
-    format: OutputFormat,
-    limit: usize,
-    author: Option<&str>,
-    grep: Option<&str>,
-    since: Option<&str>,
-    until: Option<&str>,
-    include_body: bool,
-    group_by_type: bool,
-    sort_order: SortOrder,
-    exclude_merges: bool,
-    include_stats: bool,
-    include_diff_stats: bool,
-    release_mode: bool,
-) -> Result<String> {
     let analyzer = GitAnalyzer::new(repo_path)?;
+    let changelog = render_changelog(&analyzer, options)?;
+    Ok((changelog, analyzer.override_warnings()))
+}
 
-    let commits = analyzer.get_commits(
-        limit,
-        author,
-        grep,
-        since,
-        until,
-        include_body,
-        group_by_type,
-        sort_order,
-        exclude_merges,
+fn render_changelog(analyzer: &GitAnalyzer, options: &GenOptions) -> Result<String> {
+    let format = options.format;
+    // Release feeds carry every release, not just the latest one.
+    let feed = matches!(format, OutputFormat::Atom | OutputFormat::Rss);
+
+    // Chat and email announcements always describe a single release.
+    let chat = match format {
+        OutputFormat::Slack => Some(chat::Platform::Slack),
+        OutputFormat::Discord => Some(chat::Platform::Discord),
+        OutputFormat::Teams => Some(chat::Platform::Teams),
+        _ => None,
+    };
+    let email = matches!(format, OutputFormat::Eml | OutputFormat::Mbox);
+
+    let release_range = if (options.release_mode || chat.is_some() || email) && !feed {
+        Some(release::latest_range(
+            &analyzer.repo,
+            &repository_settings(analyzer),
+        )?)
+    } else {
+        None
+    };
+
+    let csv_columns = analyzer
+        .config
+        .as_ref()
+        .map_or(config::CsvColumn::ALL.to_vec(), |c| {
+            c.output.csv_columns.clone()
+        });
+    // CSV columns are filled in whether or not `-d` was given.
+    let include_diff_stats = options.include_diff_stats
+        || (format == OutputFormat::Csv
+            && csv_columns.iter().any(|c| {
+                matches!(
+                    c,
+                    config::CsvColumn::Insertions
+                        | config::CsvColumn::Deletions
+                        | config::CsvColumn::Files
+                )
+            }));
+
+    let range = release_range.as_ref().and_then(|r| r.revspec());
+    let commits = analyzer.get_commits(&CommitFilter {
         include_diff_stats,
-    )?;
+        ..options.commit_filter(range.as_deref())
+    })?;
+
+    if let Some(name) = options.formatter.as_deref() {
+        let plugin = analyzer
+            .config
+            .as_ref()
+            .and_then(|c| c.plugins.formatters.iter().find(|p| p.name == name))
+            .ok_or_else(|| RcgenError::Config(format!("Unknown formatter plugin: {}", name)))?;
+        let stats = if options.include_stats {
+            Some(analyzer.get_statistics(&commits))
+        } else {
+            None
+        };
+        let document = ChangelogDocument {
+            commits: &commits,
+            groups: options
+                .group_by_type
+                .then(|| analyzer.group_commits(&commits)),
+            stats: stats.as_ref(),
+            release_mode: options.release_mode,
+            generated_at: chrono::Utc::now(),
+        };
+        return plugins::run_formatter(plugin, analyzer.repo.workdir(), &document);
+    }
+
+    if let OutputFormat::Keepachangelog = format {
+        return format_keepachangelog(analyzer, &commits, release_range.as_ref());
+    }
+
+    if feed {
+        return format_feed(analyzer, &commits, options.release_mode, format);
+    }
+
+    if let OutputFormat::Debian | OutputFormat::Rpm = format {
+        return format_package_changelog(analyzer, &commits, release_range.as_ref(), format);
+    }
+
+    if let OutputFormat::Csv = format {
+        return Ok(format_csv(&commits, &csv_columns));
+    }
+
+    if let OutputFormat::Adoc | OutputFormat::Rst = format {
+        let markup = match format {
+            OutputFormat::Adoc => markup::Markup::AsciiDoc,
+            _ => markup::Markup::ReStructuredText,
+        };
+        let date_format = analyzer
+            .config
+            .as_ref()
+            .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+        return Ok(markup::render(
+            markup,
+            analyzer,
+            &release::sections(analyzer, &commits, release_range.as_ref())?,
+            release::repository_url(analyzer)?.as_deref(),
+            date_format,
+        ));
+    }
+
+    if let (Some(platform), Some(range)) = (chat, &release_range) {
+        let notes = release::build_notes(analyzer, range, &commits)?;
+        let base_url = release::repository_url(analyzer)?;
+        let date_format = analyzer
+            .config
+            .as_ref()
+            .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+        let payload = chat::payload(
+            platform,
+            &notes,
+            &repository_name(analyzer, base_url.as_deref()),
+            base_url.as_deref(),
+            date_format,
+        );
+        // Payloads are posted as they are, and whitespace counts towards the
+        // platforms' size limits.
+        return serde_json::to_string(&payload).map_err(Into::into);
+    }
+
+    if let (true, Some(range)) = (email, &release_range) {
+        return format_email(analyzer, &commits, range, format);
+    }
+
+    if let OutputFormat::News = format {
+        let width = analyzer
+            .config
+            .as_ref()
+            .map_or(config::default_wrap_width(), |c| c.output.wrap_width);
+        return Ok(news::render(
+            analyzer,
+            &release::sections(analyzer, &commits, release_range.as_ref())?,
+            width,
+        ));
+    }
+
+    if let OutputFormat::Html = format {
+        return format_html(
+            analyzer,
+            &commits,
+            release_range.as_ref(),
+            options.include_stats,
+            options.theme,
+        );
+    }
 
     if commits.is_empty() {
         return match format {
             OutputFormat::Md => Ok("# No commits found\n".to_string()),
             OutputFormat::Json => Ok("[]".to_string()),
-            OutputFormat::Text => Ok("No commits found".to_string()),
+            _ => Ok("No commits found".to_string()),
         };
     }
 
-    let stats = if include_stats {
+    if let Some(range) = &release_range {
+        let notes = release::build_notes(analyzer, range, &commits)?;
+        let date_format = analyzer
+            .config
+            .as_ref()
+            .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+        return match format {
+            OutputFormat::Md => Ok(format_release_markdown(&notes, date_format)),
+            OutputFormat::Json => serde_json::to_string_pretty(&notes).map_err(Into::into),
+            _ => Ok(format_release_text(&notes, date_format)),
+        };
+    }
+
+    let stats = if options.include_stats {
         Some(analyzer.get_statistics(&commits))
     } else {
         None
     };
 
     let output = match format {
-        OutputFormat::Md => format_markdown(
-            &analyzer,
-            &commits,
-            group_by_type,
-            stats.as_ref(),
-            release_mode,
-        ),
+        OutputFormat::Md => {
+            format_markdown(analyzer, &commits, options.group_by_type, stats.as_ref())
+        }
         OutputFormat::Json => format_json(&commits, stats.as_ref())?,
-        OutputFormat::Text => format_text(&analyzer, &commits, group_by_type, stats.as_ref()),
+        _ => format_text(analyzer, &commits, options.group_by_type, stats.as_ref()),
     };
 
     Ok(output)
 }
 
-pub fn generate_stats(repo_path: &str, detailed: bool, format: OutputFormat) -> Result<String> {
+pub fn write_ndjson(
+    repo_path: &str,
+    writer: &mut dyn std::io::Write,
+    options: &GenOptions,
+) -> Result<Vec<String>> {
     let analyzer = GitAnalyzer::new(repo_path)?;
-    let commits = analyzer.get_commits(
-        0,
-        None,
-        None,
-        None,
-        None,
-        false,
-        false,
-        SortOrder::Reverse,
-        true,
-        false,
-    )?;
-    let stats = analyzer.get_statistics(&commits);
+    let range = if options.release_mode {
+        release::latest_range(&analyzer.repo, &repository_settings(&analyzer))?.revspec()
+    } else {
+        None
+    };
 
-    match format {
-        OutputFormat::Md => Ok(format_stats_markdown(&stats, detailed)),
-        OutputFormat::Json => Ok(format_stats_json(&stats, detailed)?),
-        OutputFormat::Text => Ok(format_stats_text(&stats, detailed)),
+    let mut write = |commit: CommitInfo| -> Result<()> {
+        serde_json::to_writer(&mut *writer, &commit)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    };
+
+    let filter = options.commit_filter(range.as_deref());
+    // Sorting by author needs every commit first; other orders stream.
+    if let SortOrder::Author = options.sort_order {
+        let commits = analyzer.get_commits(&filter)?;
+        for commit in commits {
+            write(commit)?;
+        }
+        return Ok(analyzer.override_warnings());
+    }
+
+    analyzer.for_each_commit(&filter, write)?;
+    Ok(analyzer.override_warnings())
+}
+
+pub fn generate_stats(
+    repo_path: &str,
+    detailed: bool,
+    format: StatsFormat,
+    theme: Theme,
+) -> Result<(String, Vec<String>)> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let stats = repository_stats(&analyzer)?;
+
+    let output = match format {
+        StatsFormat::Md => format_stats_markdown(&stats, detailed),
+        StatsFormat::Json => format_stats_json(&stats, detailed)?,
+        StatsFormat::Csv => format_stats_csv(&stats),
+        StatsFormat::Ndjson => {
+            let mut buffer = Vec::new();
+            write_author_lines(&stats, &mut buffer)?;
+            String::from_utf8_lossy(&buffer).trim_end().to_string()
+        }
+        StatsFormat::Html => html::page(
+            "Repository Statistics",
+            theme,
+            &html::render_stats(&stats, detailed),
+        ),
+        StatsFormat::Text => format_stats_text(&stats, detailed),
+    };
+    Ok((output, analyzer.override_warnings()))
+}
+
+// Writes one line per author as soon as the statistics are computed.
+pub fn write_stats_ndjson(repo_path: &str, writer: &mut dyn std::io::Write) -> Result<Vec<String>> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    write_author_lines(&repository_stats(&analyzer)?, writer)?;
+    Ok(analyzer.override_warnings())
+}
+
+fn write_author_lines(
+    stats: &crate::core::RepositoryStats,
+    writer: &mut dyn std::io::Write,
+) -> Result<()> {
+    for author in &stats.authors {
+        serde_json::to_writer(&mut *writer, author)?;
+        writer.write_all(b"\n")?;
     }
+    Ok(())
+}
+
+fn repository_stats(analyzer: &GitAnalyzer) -> Result<crate::core::RepositoryStats> {
+    let commits = analyzer.get_commits(&CommitFilter {
+        exclude_merges: true,
+        // Every format reports insertions, deletions and changed files.
+        include_diff_stats: true,
+        ..Default::default()
+    })?;
+    Ok(analyzer.get_statistics(&commits))
 }
 
 pub fn init_config(repo_path: &str, force: bool) -> Result<()> {
-    crate::config::Config::init(repo_path, force)
+    Config::init(repo_path, force)
 }
 
-pub fn preview_changelog(repo_path: &str, limit: usize) -> Result<String> {
+pub fn preview_changelog(repo_path: &str, limit: usize) -> Result<(String, Vec<String>)> {
     let analyzer = GitAnalyzer::new(repo_path)?;
-    let commits = analyzer.get_commits(
+    let commits = analyzer.get_commits(&CommitFilter {
         limit,
-        None,
-        None,
-        None,
-        None,
-        false,
-        true,
-        SortOrder::Reverse,
-        true,
-        false,
-    )?;
+        exclude_merges: true,
+        ..Default::default()
+    })?;
 
     let mut output = String::new();
     output.push_str("Preview of last commits:\n\n");
@@ -145,15 +430,1153 @@ pub fn preview_changelog(repo_path: &str, limit: usize) -> Result<String> {
         output.push('\n');
     }
 
+    Ok((output, analyzer.override_warnings()))
+}
+
+pub fn lint_commits(
+    repo_path: &str,
+    range: Option<&str>,
+    message_file: Option<&str>,
+) -> Result<LintReport> {
+    if let Some(message_file) = message_file {
+        let config = Config::load(repo_path)?.unwrap_or_default();
+        let message = lint::clean_message(&std::fs::read_to_string(message_file)?);
+        let linter = Linter::new(config);
+        return Ok(LintReport {
+            results: vec![linter.lint(None, &message)],
+        });
+    }
+
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let linter = Linter::new(analyzer.config.clone().unwrap_or_default());
+    let mut results = Vec::new();
+
+    for oid in analyzer.commits_in_range(range.unwrap_or("HEAD"))? {
+        let commit = analyzer.repo.find_commit(oid)?;
+
+        // Merge commits carry generated messages and are not linted.
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        let hash = oid.to_string();
+        let message = commit.message().unwrap_or("");
+        results.push(linter.lint(Some(hash[..8].to_string()), message));
+    }
+
+    Ok(LintReport { results })
+}
+
+pub fn format_lint_report(report: &LintReport, format: LintFormat) -> Result<String> {
+    if let LintFormat::Json = format {
+        #[derive(serde::Serialize)]
+        struct Output<'a> {
+            results: &'a [lint::LintResult],
+            checked: usize,
+            errors: usize,
+            warnings: usize,
+        }
+
+        let output = Output {
+            results: &report.results,
+            checked: report.results.len(),
+            errors: report.error_count(),
+            warnings: report.warning_count(),
+        };
+
+        return serde_json::to_string_pretty(&output).map_err(Into::into);
+    }
+
+    let mut output = String::new();
+
+    for result in &report.results {
+        if result.violations.is_empty() {
+            continue;
+        }
+
+        let subject = match &result.commit {
+            Some(hash) => format!("{} {}", hash, result.summary),
+            None => result.summary.clone(),
+        };
+
+        match format {
+            LintFormat::Md => output.push_str(&format!("- `{}`\n", subject)),
+            _ => output.push_str(&format!("{}\n", subject)),
+        }
+
+        for violation in &result.violations {
+            let severity = match violation.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+
+            match format {
+                LintFormat::Md => output.push_str(&format!(
+                    "  - **{}** `{}`: {}\n",
+                    severity, violation.rule, violation.message
+                )),
+                _ => output.push_str(&format!(
+                    "    {}[{}]: {}\n",
+                    severity, violation.rule, violation.message
+                )),
+            }
+        }
+    }
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    output.push_str(&format!(
+        "{} commit(s) checked, {} error(s), {} warning(s)",
+        report.results.len(),
+        report.error_count(),
+        report.warning_count()
+    ));
+
     Ok(output)
 }
 
+pub fn install_hooks(repo_path: &str, post_commit: bool, on_tag: bool) -> Result<Vec<String>> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let mut names = vec!["commit-msg"];
+    if post_commit {
+        names.push("post-commit");
+    }
+    if on_tag {
+        names.push("reference-transaction");
+    }
+    hooks::install(&analyzer.repo, &names)
+}
+
+pub fn uninstall_hooks(repo_path: &str) -> Result<Vec<String>> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    hooks::uninstall(&analyzer.repo)
+}
+
+pub fn run_hook(repo_path: &str, hook: &str, args: &[String]) -> Result<HookOutcome> {
+    match hook {
+        "commit-msg" => {
+            let message_file = args.first().ok_or_else(|| {
+                RcgenError::Config("commit-msg hook requires a message file".to_string())
+            })?;
+            let report = lint_commits(repo_path, None, Some(message_file))?;
+            let output = if report.error_count() + report.warning_count() > 0 {
+                format_lint_report(&report, LintFormat::Text)?
+            } else {
+                String::new()
+            };
+            Ok(HookOutcome {
+                output,
+                success: !report.has_errors(),
+            })
+        }
+        "post-commit" => {
+            // Tags are usually created after the commit, so this only fires when
+            // HEAD was already tagged, e.g. after amending a release commit.
+            let analyzer = GitAnalyzer::new(repo_path)?;
+            let pattern = release_tag_pattern(repo_path)?;
+            let released = hooks::head_tags(&analyzer.repo)?
+                .iter()
+                .any(|tag| utils::matches_tag_pattern(&pattern, tag));
+            let output = if released {
+                format!("Changelog written to {}", regenerate_changelog(repo_path)?)
+            } else {
+                String::new()
+            };
+            Ok(HookOutcome {
+                output,
+                success: true,
+            })
+        }
+        "reference-transaction" => {
+            if args.first().map(String::as_str) != Some("committed") {
+                return Ok(HookOutcome {
+                    output: String::new(),
+                    success: true,
+                });
+            }
+            let mut input = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+            let pattern = release_tag_pattern(repo_path)?;
+            let released = hooks::created_tags(&input)
+                .iter()
+                .any(|tag| utils::matches_tag_pattern(&pattern, tag));
+            let output = if released {
+                format!("Changelog written to {}", regenerate_changelog(repo_path)?)
+            } else {
+                String::new()
+            };
+            Ok(HookOutcome {
+                output,
+                success: true,
+            })
+        }
+        _ => Err(RcgenError::Config(format!("Unknown hook: {}", hook))),
+    }
+}
+
+fn release_tag_pattern(repo_path: &str) -> Result<String> {
+    let config = Config::load(repo_path)?.unwrap_or_default();
+    Ok(config
+        .repository
+        .tag_pattern
+        .unwrap_or_else(|| "*".to_string()))
+}
+
+fn regenerate_changelog(repo_path: &str) -> Result<String> {
+    let config = Config::load(repo_path)?.unwrap_or_default();
+    let (changelog, _) = generate_changelog(
+        repo_path,
+        &GenOptions {
+            include_body: config.output.include_body,
+            group_by_type: config.grouping.enabled,
+            exclude_merges: config.output.exclude_merges,
+            include_diff_stats: config.output.include_diff_stats,
+            ..Default::default()
+        },
+    )?;
+    let path = std::path::Path::new(repo_path).join(&config.output.changelog_path);
+    std::fs::write(&path, changelog)?;
+    Ok(path.display().to_string())
+}
+
+pub fn release(
+    repo_path: &str,
+    dry_run: bool,
+    bump: Option<Bump>,
+    version: Option<&str>,
+) -> Result<String> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let config = analyzer.config.clone().unwrap_or_default();
+    let workdir = analyzer
+        .repo
+        .workdir()
+        .ok_or_else(|| RcgenError::Release("cannot release from a bare repository".to_string()))?
+        .to_path_buf();
+
+    let last = release::last_release(&analyzer.repo, &config.repository)?;
+    let range = last.as_ref().map(|l| format!("{}..HEAD", l.tag));
+
+    let commits = analyzer.get_commits(&CommitFilter {
+        range: range.as_deref(),
+        exclude_merges: config.output.exclude_merges,
+        ..Default::default()
+    })?;
+    if commits.is_empty() {
+        return Err(RcgenError::Release(match &last {
+            Some(last) => format!("no changes since {}", last.tag),
+            None => "no commits to release".to_string(),
+        }));
+    }
+
+    let current = last
+        .as_ref()
+        .map(|l| l.version.clone())
+        .unwrap_or_else(|| Version::new(0, 0, 0));
+    let (next, reason) = match version {
+        Some(requested) => {
+            let next = Version::from_tag(requested, None)
+                .filter(|(prefix, _)| prefix.is_empty() || prefix.ends_with(['v', 'V']))
+                .map(|(_, version)| version)
+                .ok_or_else(|| {
+                    RcgenError::Release(format!("'{}' is not a semantic version", requested))
+                })?;
+            (next, "requested".to_string())
+        }
+        None => {
+            let (bump, reason) = match bump {
+                Some(bump) => (bump, "requested"),
+                None => (release::compute_bump(&commits, &current), "computed"),
+            };
+            let name = match bump {
+                Bump::Major => "major",
+                Bump::Minor => "minor",
+                Bump::Patch => "patch",
+            };
+            (current.bump(bump), format!("{} bump, {}", name, reason))
+        }
+    };
+    if last.is_some() && next <= current {
+        return Err(RcgenError::Release(format!(
+            "{} is not newer than the current version {}",
+            next, current
+        )));
+    }
+
+    let prefix = match &last {
+        Some(last) => last.prefix.as_str(),
+        None => config.repository.tag_prefix.as_deref().unwrap_or("v"),
+    };
+    let tag = format!("{}{}", prefix, next);
+    if analyzer
+        .repo
+        .refname_to_id(&format!("refs/tags/{}", tag))
+        .is_ok()
+    {
+        return Err(RcgenError::Release(format!("tag {} already exists", tag)));
+    }
+
+    let range = release::ReleaseRange {
+        tag: Some(tag.clone()),
+        previous: last.as_ref().map(|l| l.tag.clone()),
+        date: chrono::Local::now().fixed_offset(),
+    };
+    let notes = format_release_markdown(
+        &release::build_notes(&analyzer, &range, &commits)?,
+        &config.templates.date_format,
+    );
+
+    let new_version = next.to_string();
+    let mut updates = Vec::new();
+    for file in release::version_files(&workdir, &config.release) {
+        updates.push(release::plan_file_update(&workdir, &file, &new_version)?);
+    }
+
+    let changelog_path = workdir.join(&config.output.changelog_path);
+    let existing = if changelog_path.exists() {
+        Some(std::fs::read_to_string(&changelog_path)?)
+    } else {
+        None
+    };
+    let header = config
+        .templates
+        .header
+        .clone()
+        .unwrap_or_else(|| "# Changelog\n".to_string());
+    let changelog = release::prepend_changelog(existing.as_deref(), &header, &notes);
+
+    let message = config
+        .release
+        .commit_message
+        .replace("{version}", &new_version)
+        .replace("{tag}", &tag);
+
+    if dry_run {
+        let mut output = format!(
+            "Release {} ({}; previous: {}, {} commit(s))\n",
+            tag,
+            reason,
+            last.as_ref().map_or("none", |l| l.tag.as_str()),
+            commits.len()
+        );
+        for update in &updates {
+            output.push_str(&format!(
+                "Would update {}: {} -> {}\n",
+                update.path, update.old_version, new_version
+            ));
+        }
+        output.push_str(&format!(
+            "Would {} {}\n",
+            if existing.is_some() {
+                "prepend release notes to"
+            } else {
+                "create"
+            },
+            config.output.changelog_path
+        ));
+        output.push_str(&format!("Would commit: {}\n", message));
+        output.push_str(&format!("Would create annotated tag {}\n\n", tag));
+        output.push_str(&notes);
+        return Ok(output.trim_end().to_string());
+    }
+
+    let mut files: Vec<(String, String)> =
+        updates.into_iter().map(|u| (u.path, u.content)).collect();
+    files.push((config.output.changelog_path.clone(), changelog));
+    let paths: Vec<String> = files.iter().map(|(path, _)| path.clone()).collect();
+    release::ensure_clean(&analyzer.repo, &paths)?;
+    let signature = analyzer.repo.signature()?;
+    let commit = release::commit_and_tag(
+        &analyzer.repo,
+        &signature,
+        &files,
+        &message,
+        &tag,
+        notes.trim_end(),
+    )?;
+
+    Ok(format!("Released {} at {}", tag, &commit.to_string()[..8]))
+}
+
+pub fn which_release(repo_path: &str, query: &str, format: WhichReleaseFormat) -> Result<String> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let repo = &analyzer.repo;
+    let date_format = analyzer
+        .config
+        .as_ref()
+        .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+
+    let commits = release::find_commits(repo, query)?;
+    if commits.is_empty() {
+        return Err(RcgenError::NoCommits);
+    }
+    let tags = release::release_tags(repo, &repository_settings(&analyzer))?;
+
+    #[derive(serde::Serialize)]
+    struct Match {
+        commit: String,
+        short_hash: String,
+        summary: String,
+        release: Option<String>,
+        date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    }
+
+    let mut matches = Vec::new();
+    for oid in commits {
+        let commit = repo.find_commit(oid)?;
+        let hash = oid.to_string();
+        let tag = release::first_release_containing(repo, &tags, oid)?;
+        matches.push(Match {
+            short_hash: hash[..8].to_string(),
+            commit: hash,
+            summary: commit.summary().unwrap_or("").to_string(),
+            date: tag.as_ref().map(|t| t.date),
+            release: tag.map(|t| t.name),
+        });
+    }
+
+    if let WhichReleaseFormat::Json = format {
+        return serde_json::to_string_pretty(&matches).map_err(Into::into);
+    }
+
+    let lines: Vec<String> = matches
+        .iter()
+        .map(|m| {
+            let release = match (&m.release, m.date) {
+                (Some(tag), Some(date)) => match format {
+                    WhichReleaseFormat::Md => format!("**{}** ({})", tag, date.format(date_format)),
+                    _ => format!("{} ({})", tag, date.format(date_format)),
+                },
+                _ => "unreleased".to_string(),
+            };
+            match format {
+                WhichReleaseFormat::Md => {
+                    format!("- `{}` {} → {}", m.short_hash, m.summary, release)
+                }
+                _ => format!("{} {} -> {}", m.short_hash, m.summary, release),
+            }
+        })
+        .collect();
+
+    Ok(lines.join("\n"))
+}
+
+pub fn release_timeline(repo_path: &str, format: TagsFormat) -> Result<String> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let timeline = release::timeline(&analyzer, &repository_settings(&analyzer))?;
+    let date_format = analyzer
+        .config
+        .as_ref()
+        .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+
+    if let TagsFormat::Json = format {
+        return serde_json::to_string_pretty(&timeline).map_err(Into::into);
+    }
+
+    let headers = [
+        "Tag",
+        "Date",
+        "Commits",
+        "Authors",
+        "Insertions",
+        "Deletions",
+        "Days",
+    ];
+    let rows: Vec<[String; 7]> = timeline
+        .iter()
+        .map(|entry| {
+            [
+                entry.tag.clone(),
+                entry.date.format(date_format).to_string(),
+                entry.commits.to_string(),
+                entry.authors.to_string(),
+                entry.insertions.to_string(),
+                entry.deletions.to_string(),
+                entry
+                    .days_since_previous
+                    .map_or_else(String::new, |d| d.to_string()),
+            ]
+        })
+        .collect();
+
+    let mut output = String::new();
+
+    match format {
+        TagsFormat::Csv => {
+            output.push_str(
+                "tag,date,previous,commits,authors,insertions,deletions,days_since_previous\n",
+            );
+            for (entry, row) in timeline.iter().zip(&rows) {
+                let fields = [
+                    utils::csv_field(&row[0]),
+                    utils::csv_field(&entry.date.to_rfc3339()),
+                    utils::csv_field(entry.previous.as_deref().unwrap_or("")),
+                    row[2].clone(),
+                    row[3].clone(),
+                    row[4].clone(),
+                    row[5].clone(),
+                    row[6].clone(),
+                ];
+                output.push_str(&fields.join(","));
+                output.push('\n');
+            }
+        }
+        TagsFormat::Md => {
+            output.push_str(&format!("| {} |\n", headers.join(" | ")));
+            output.push_str("| --- | --- | ---: | ---: | ---: | ---: | ---: |\n");
+            for row in &rows {
+                output.push_str(&format!("| {} |\n", row.join(" | ")));
+            }
+        }
+        _ => {
+            if rows.is_empty() {
+                return Ok("No release tags found".to_string());
+            }
+
+            let tag_width = rows
+                .iter()
+                .map(|r| r[0].chars().count())
+                .chain([headers[0].len()])
+                .max()
+                .unwrap_or(0);
+            let date_width = rows
+                .iter()
+                .map(|r| r[1].chars().count())
+                .chain([headers[1].len()])
+                .max()
+                .unwrap_or(0);
+
+            output.push_str("RELEASE TIMELINE\n");
+            output.push_str(&"=".repeat(80));
+            output.push('\n');
+            output.push_str(&format!(
+                "{:<tw$}  {:<dw$}  {:>7}  {:>7}  {:>10}  {:>10}  {:>5}\n",
+                headers[0],
+                headers[1],
+                headers[2],
+                headers[3],
+                headers[4],
+                headers[5],
+                headers[6],
+                tw = tag_width,
+                dw = date_width
+            ));
+            for row in &rows {
+                output.push_str(&format!(
+                    "{:<tw$}  {:<dw$}  {:>7}  {:>7}  {:>10}  {:>10}  {:>5}\n",
+                    row[0],
+                    row[1],
+                    row[2],
+                    row[3],
+                    format!("+{}", row[4]),
+                    format!("-{}", row[5]),
+                    row[6],
+                    tw = tag_width,
+                    dw = date_width
+                ));
+            }
+        }
+    }
+
+    Ok(output.trim_end().to_string())
+}
+
+pub fn export_sqlite(repo_path: &str, database: &str) -> Result<String> {
+    let analyzer = GitAnalyzer::new(repo_path)?;
+    let summary = export::export_sqlite(&analyzer, std::path::Path::new(database))?;
+    Ok(format!(
+        "Exported {} new commit(s) to {} ({} already present, {} tag(s))",
+        summary.added, database, summary.existing, summary.tags
+    ))
+}
+
+fn format_keepachangelog(
+    analyzer: &GitAnalyzer,
+    commits: &[CommitInfo],
+    release_range: Option<&release::ReleaseRange>,
+) -> Result<String> {
+    let config = analyzer.config.clone().unwrap_or_default();
+    let repo = &analyzer.repo;
+    let categorizer = keepachangelog::Categorizer::new(&config.keepachangelog)?;
+    let base_url = release::repository_url(analyzer)?;
+    let sections = release::sections(analyzer, commits, release_range)?;
+
+    let existing = {
+        let path = repo
+            .workdir()
+            .map(|dir| dir.join(&config.output.changelog_path))
+            .filter(|path| path.exists());
+        match path {
+            Some(path) => keepachangelog::Document::parse(&std::fs::read_to_string(path)?),
+            None => keepachangelog::Document::default(),
+        }
+    };
+
+    let mut document = keepachangelog::Document {
+        preamble: if existing.preamble.trim().is_empty() {
+            keepachangelog::DEFAULT_PREAMBLE.to_string()
+        } else {
+            existing.preamble.clone()
+        },
+        ..Default::default()
+    };
+
+    for release::ReleaseSection {
+        label,
+        tag,
+        previous,
+        date,
+        commits: range_commits,
+        ..
+    } in &sections
+    {
+        // Released sections that are already in the changelog may have been
+        // edited by hand, so they are kept as they are.
+        let text = match existing.section(label) {
+            Some(section) if tag.is_some() => section.text.clone(),
+            _ => keepachangelog::render_section(
+                &categorizer,
+                label,
+                date.map(|d| d.format("%Y-%m-%d").to_string()).as_deref(),
+                range_commits,
+            ),
+        };
+        document.sections.push(keepachangelog::Section {
+            label: label.clone(),
+            text,
+        });
+
+        let target = tag.as_deref().unwrap_or("HEAD");
+        let link = match (existing.link(label), &base_url, previous) {
+            (Some(link), _, _) => Some(link.to_string()),
+            (None, Some(base), Some(previous)) => {
+                Some(release::compare_link(base, previous, target))
+            }
+            (None, Some(base), None) if tag.is_some() => Some(release::tag_link(base, target)),
+            _ => None,
+        };
+        if let Some(link) = link {
+            let key = if tag.is_some() {
+                label.clone()
+            } else {
+                label.to_lowercase()
+            };
+            document.links.push((key, link));
+        }
+    }
+
+    if release_range.is_some() {
+        let mut output = document
+            .sections
+            .iter()
+            .map(|s| s.text.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !document.links.is_empty() {
+            output.push('\n');
+            for (label, url) in &document.links {
+                output.push_str(&format!("[{}]: {}\n", label, url));
+            }
+        }
+        return Ok(output);
+    }
+
+    // Older sections and links rcgen knows nothing about, e.g. from before the
+    // project used tags, are carried over unchanged.
+    for section in &existing.sections {
+        if document.section(&section.label).is_none() {
+            document.sections.push(section.clone());
+        }
+    }
+    for (label, url) in &existing.links {
+        if document.link(label).is_none() {
+            document.links.push((label.clone(), url.clone()));
+        }
+    }
+
+    Ok(document.render())
+}
+
+fn format_html(
+    analyzer: &GitAnalyzer,
+    commits: &[CommitInfo],
+    release_range: Option<&release::ReleaseRange>,
+    include_stats: bool,
+    theme: Theme,
+) -> Result<String> {
+    let sections = release::sections(analyzer, commits, release_range)?;
+    let base_url = release::repository_url(analyzer)?;
+    let date_format = analyzer
+        .config
+        .as_ref()
+        .map_or("%Y-%m-%d", |c| c.templates.date_format.as_str());
+
+    let mut body = html::render_changelog(analyzer, &sections, base_url.as_deref(), date_format);
+    if include_stats && !commits.is_empty() {
+        body.push_str(&html::render_stats(
+            &analyzer.get_statistics(commits),
+            false,
+        ));
+    }
+
+    let title = match release_range {
+        Some(range) if range.tag.is_some() => format!("Release {}", sections[0].label),
+        Some(_) => "Unreleased Changes".to_string(),
+        None => "Changelog".to_string(),
+    };
+    Ok(html::page(&title, theme, &body))
+}
+
+fn format_feed(
+    analyzer: &GitAnalyzer,
+    commits: &[CommitInfo],
+    release_mode: bool,
+    format: OutputFormat,
+) -> Result<String> {
+    let templates = analyzer.config.clone().unwrap_or_default().templates;
+    let base_url = release::repository_url(analyzer)?;
+    let name = repository_name(analyzer, base_url.as_deref());
+    let kind = if release_mode { "releases" } else { "commits" };
+
+    let mut entries = Vec::new();
+    if release_mode {
+        for section in release::sections(analyzer, commits, None)? {
+            let (Some(tag), Some(date)) = (&section.tag, section.date) else {
+                continue;
+            };
+            let mut content = String::new();
+            for group in analyzer.group_commits(&section.commits) {
+                content.push_str(&format!("<h3>{}</h3>\n<ul>\n", html::escape(&group.name)));
+                for commit in &group.commits {
+                    content.push_str(&format!(
+                        "<li>{}{} ({})</li>\n",
+                        if commit.breaking {
+                            "<strong>BREAKING:</strong> "
+                        } else {
+                            ""
+                        },
+                        html::escape(&commit.summary),
+                        html::commit_reference(commit, base_url.as_deref())
+                    ));
+                }
+                content.push_str("</ul>\n");
+            }
+            if let (Some(base), Some(previous)) = (&base_url, &section.previous) {
+                content.push_str(&format!(
+                    "<p><a href=\"{}\">Full Changelog</a></p>\n",
+                    html::escape(&release::compare_link(base, previous, tag))
+                ));
+            }
+            let link = base_url.as_deref().map(|base| release::tag_link(base, tag));
+            entries.push(feed::Entry {
+                id: link
+                    .clone()
+                    .unwrap_or_else(|| format!("urn:rcgen:tag:{}", tag)),
+                permalink: link.is_some(),
+                title: format!("{} {}", name, section.label),
+                link,
+                date,
+                author: None,
+                categories: Vec::new(),
+                content,
+            });
+        }
+    } else {
+        for commit in commits {
+            let mut content = format!("<p>{}</p>\n", html::escape(&commit.summary));
+            if let Some(body) = &commit.body
+                && !body.trim().is_empty()
+            {
+                content.push_str(&format!("<pre>{}</pre>\n", html::escape(body.trim())));
+            }
+            for note in &commit.notes {
+                content.push_str(&format!(
+                    "<blockquote><strong>Note:</strong> {}</blockquote>\n",
+                    html::escape(note)
+                ));
+            }
+            let link = base_url
+                .as_deref()
+                .map(|base| release::commit_link(base, &commit.hash));
+            entries.push(feed::Entry {
+                id: link
+                    .clone()
+                    .unwrap_or_else(|| format!("urn:rcgen:commit:{}", commit.hash)),
+                permalink: link.is_some(),
+                title: commit.summary.clone(),
+                link,
+                date: commit.date,
+                author: Some(feed::Person {
+                    name: commit.author.name.clone(),
+                    email: Some(commit.author.email.clone()),
+                }),
+                categories: commit.commit_type.iter().cloned().collect(),
+                content,
+            });
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.date));
+
+    let feed = feed::Feed {
+        id: templates
+            .feed_link
+            .clone()
+            .unwrap_or_else(|| format!("urn:rcgen:{}:{}", name, kind)),
+        title: templates
+            .feed_title
+            .clone()
+            .unwrap_or_else(|| format!("{} {}", name, kind)),
+        description: format!("Latest {} of {}", kind, name),
+        link: base_url,
+        self_link: templates.feed_link,
+        author: feed::Person::parse(templates.feed_author.as_deref().unwrap_or(&name)),
+        updated: entries
+            .first()
+            .map(|e| e.date)
+            .unwrap_or_else(|| chrono::Utc::now().fixed_offset()),
+        entries,
+    };
+
+    Ok(match format {
+        OutputFormat::Rss => feed::rss(&feed),
+        _ => feed::atom(&feed),
+    })
+}
+
+fn format_package_changelog(
+    analyzer: &GitAnalyzer,
+    commits: &[CommitInfo],
+    release_range: Option<&release::ReleaseRange>,
+    format: OutputFormat,
+) -> Result<String> {
+    let settings = analyzer.config.clone().unwrap_or_default().packaging;
+    let maintainer = match &settings.maintainer {
+        Some(maintainer) => maintainer.clone(),
+        None => {
+            let signature = analyzer.repo.signature().map_err(|_| {
+                RcgenError::Config(
+                    "packaging.maintainer is not set and git has no user.name/user.email"
+                        .to_string(),
+                )
+            })?;
+            format!(
+                "{} <{}>",
+                signature.name().unwrap_or_default(),
+                signature.email().unwrap_or_default()
+            )
+        }
+    };
+
+    // Package changelogs only describe tagged releases.
+    let mut releases = Vec::new();
+    for section in release::sections(analyzer, commits, release_range)? {
+        let (Some(tag), Some(date)) = (&section.tag, section.date) else {
+            continue;
+        };
+        let upstream = packaging::upstream_version(&section.label).ok_or_else(|| {
+            RcgenError::Release(format!("{} is not a valid package version", tag))
+        })?;
+        let version = packaging::package_version(&upstream, &settings.revision);
+        let changes = analyzer
+            .group_commits(&section.commits)
+            .into_iter()
+            .flat_map(|group| group.commits)
+            .map(|commit| commit.summary)
+            .collect();
+        releases.push(packaging::PackageRelease {
+            version,
+            upstream,
+            date,
+            changes,
+        });
+    }
+
+    if releases.is_empty()
+        && let Some(range) = release_range
+        && range.tag.is_none()
+    {
+        return Err(RcgenError::Release(
+            "HEAD is not tagged, so there is no release to package".to_string(),
+        ));
+    }
+
+    Ok(match format {
+        OutputFormat::Debian => {
+            let package = match &settings.package {
+                Some(package) => package.clone(),
+                None => packaging::package_name(&repository_name(
+                    analyzer,
+                    release::repository_url(analyzer)?.as_deref(),
+                )),
+            };
+            packaging::debian(
+                &package,
+                &settings.distribution,
+                &settings.urgency,
+                &maintainer,
+                &releases,
+            )
+        }
+        // A full changelog is a ready-made spec section; a single release is
+        // meant to be inserted below an existing `%changelog`.
+        _ if release_range.is_some() => packaging::rpm(&maintainer, &releases),
+        _ => format!("%changelog\n{}", packaging::rpm(&maintainer, &releases)),
+    })
+}
+
+fn format_email(
+    analyzer: &GitAnalyzer,
+    commits: &[CommitInfo],
+    range: &release::ReleaseRange,
+    format: OutputFormat,
+) -> Result<String> {
+    let config = analyzer.config.clone().unwrap_or_default();
+    let settings = config.email;
+    if settings.to.is_empty() {
+        return Err(RcgenError::Config("email.to is not set".to_string()));
+    }
+    let from = match settings.from {
+        Some(from) => from,
+        None => {
+            let signature = analyzer.repo.signature().map_err(|_| {
+                RcgenError::Config(
+                    "email.from is not set and git has no user.name/user.email".to_string(),
+                )
+            })?;
+            format!(
+                "{} <{}>",
+                signature.name().unwrap_or_default(),
+                signature.email().unwrap_or_default()
+            )
+        }
+    };
+
+    let base_url = release::repository_url(analyzer)?;
+    let project = repository_name(analyzer, base_url.as_deref());
+    let date_format = config.templates.date_format.as_str();
+    let notes = release::build_notes(analyzer, range, commits)?;
+    let sections = release::sections(analyzer, commits, Some(range))?;
+    let version = sections
+        .first()
+        .map_or(notes.version.clone(), |s| s.label.clone());
+
+    let subject = settings
+        .subject
+        .replace("{project}", &project)
+        .replace("{version}", &version)
+        .replace("{tag}", &notes.version);
+    let title = format!("{} {}", project, version);
+    let html = html::page(
+        &title,
+        Theme::Light,
+        &html::render_changelog(analyzer, &sections, base_url.as_deref(), date_format),
+    );
+    // The id only depends on the release, so regenerating the announcement
+    // does not make it look like a different message.
+    let domain = feed::Person::parse(&from)
+        .email
+        .and_then(|email| email.rsplit_once('@').map(|(_, domain)| domain.to_string()))
+        .unwrap_or_else(|| "localhost".to_string());
+    let message_id = format!(
+        "{}.{}.rcgen@{}",
+        packaging::package_name(&title).replace('-', "."),
+        notes.date.timestamp(),
+        domain
+    );
+
+    let message = email::Message {
+        from,
+        to: settings.to,
+        cc: settings.cc,
+        subject,
+        date: chrono::Local::now().fixed_offset(),
+        message_id,
+        text: format_release_text(&notes, date_format),
+        html,
+    };
+    Ok(match format {
+        OutputFormat::Mbox => email::mbox(&message),
+        _ => email::eml(&message),
+    })
+}
+
+fn repository_name(analyzer: &GitAnalyzer, base_url: Option<&str>) -> String {
+    base_url
+        .and_then(|url| url.rsplit('/').next())
+        .or_else(|| {
+            analyzer
+                .repo
+                .workdir()
+                .and_then(|dir| dir.file_name())
+                .and_then(|name| name.to_str())
+        })
+        .unwrap_or("repository")
+        .to_string()
+}
+
+fn repository_settings(analyzer: &GitAnalyzer) -> config::RepositoryConfig {
+    analyzer.config.clone().unwrap_or_default().repository
+}
+
+fn format_release_markdown(notes: &release::ReleaseNotes, date_format: &str) -> String {
+    let mut output = format!(
+        "## [{}] - {}\n\n",
+        notes.version,
+        notes.date.format(date_format)
+    );
+
+    if let Some(details) = &notes.tag_details {
+        if let Some(summary) = details.summary(&notes.version) {
+            output.push_str(&format!("{}\n\n", summary));
+        }
+        if let Some(tagger) = &details.tagger {
+            output.push_str(&format!(
+                "_Tagged by {} on {} ({})_\n\n",
+                tagger.name,
+                details.date.format(date_format),
+                if details.signed { "signed" } else { "unsigned" }
+            ));
+        }
+    }
+
+    output.push_str(&format!(
+        "This release contains {} commit(s) from {} contributor(s).\n\n",
+        notes.commit_count, notes.contributor_count
+    ));
+
+    if !notes.highlights.is_empty() {
+        output.push_str("### Highlights\n\n");
+        for highlight in &notes.highlights {
+            output.push_str(&format!("- {}\n", highlight));
+        }
+        output.push('\n');
+    }
+
+    if !notes.breaking.is_empty() {
+        output.push_str("### ⚠️ Breaking Changes\n\n");
+        for commit in &notes.breaking {
+            output.push_str(&format!("- {} (`{}`)\n", commit.summary, commit.short_hash));
+            for note in &commit.notes {
+                output.push_str(&format!("  > {}\n", note));
+            }
+        }
+        output.push('\n');
+    }
+
+    for group in &notes.groups {
+        output.push_str(&format!("### {}\n\n", group.name));
+
+        for commit in &group.commits {
+            output.push_str(&format_markdown_commit(commit));
+        }
+
+        output.push('\n');
+    }
+
+    if !notes.new_contributors.is_empty() {
+        output.push_str("### New Contributors\n\n");
+        for contributor in &notes.new_contributors {
+            output.push_str(&format!(
+                "- {} made their first contribution in {}\n",
+                contributor.author.name,
+                contributor.markdown_commit()
+            ));
+        }
+        output.push('\n');
+    }
+
+    if let Some(url) = &notes.compare_url {
+        output.push_str(&format!("**Full Changelog**: {}\n", url));
+    }
+
+    output
+}
+
+fn format_release_text(notes: &release::ReleaseNotes, date_format: &str) -> String {
+    let mut output = String::new();
+
+    let heading = format!("{} - {}", notes.version, notes.date.format(date_format));
+    output.push_str(&format!("{}\n", heading));
+    output.push_str(&"=".repeat(heading.chars().count()));
+    output.push('\n');
+
+    if let Some(details) = &notes.tag_details {
+        if let Some(summary) = details.summary(&notes.version) {
+            output.push_str(&format!("\n{}\n", summary));
+        }
+        if let Some(tagger) = &details.tagger {
+            output.push_str(&format!(
+                "\nTagged by {} <{}> on {} ({})\n",
+                tagger.name,
+                tagger.email,
+                details.date.format(date_format),
+                if details.signed { "signed" } else { "unsigned" }
+            ));
+        }
+    }
+
+    output.push_str(&format!(
+        "\nThis release contains {} commit(s) from {} contributor(s).\n",
+        notes.commit_count, notes.contributor_count
+    ));
+
+    let section = |output: &mut String, title: &str| {
+        output.push_str(&format!("\n{}\n", title.to_uppercase()));
+        output.push_str(&"-".repeat(title.chars().count()));
+        output.push('\n');
+    };
+
+    if !notes.highlights.is_empty() {
+        section(&mut output, "Highlights");
+        for highlight in &notes.highlights {
+            output.push_str(&format!("* {}\n", highlight));
+        }
+    }
+
+    if !notes.breaking.is_empty() {
+        section(&mut output, "Breaking Changes");
+        for commit in &notes.breaking {
+            output.push_str(&format!("* {} [{}]\n", commit.summary, commit.short_hash));
+            for note in &commit.notes {
+                output.push_str(&format!("    {}\n", note));
+            }
+        }
+    }
+
+    for group in &notes.groups {
+        section(&mut output, &group.name);
+        for commit in &group.commits {
+            output.push_str(&format_text_commit(commit));
+        }
+    }
+
+    if !notes.new_contributors.is_empty() {
+        section(&mut output, "New Contributors");
+        for contributor in &notes.new_contributors {
+            output.push_str(&format!(
+                "* {} made their first contribution in {}\n",
+                contributor.author.name,
+                contributor
+                    .url
+                    .as_deref()
+                    .unwrap_or(&contributor.short_hash)
+            ));
+        }
+    }
+
+    if let Some(url) = &notes.compare_url {
+        output.push_str(&format!("\nFull Changelog: {}\n", url));
+    }
+
+    output
+}
+
 fn format_markdown(
     analyzer: &GitAnalyzer,
     commits: &[CommitInfo],
     group_by_type: bool,
     stats: Option<&crate::core::RepositoryStats>,
-    release_mode: bool,
 ) -> String {
     let mut output = String::new();
 
@@ -167,10 +1590,6 @@ fn format_markdown(
         output.push_str("# Changelog\n\n");
     }
 
-    if release_mode {
-        output.push_str("## Release Notes\n\n");
-    }
-
     // Group commits or list them
     if group_by_type {
         let groups = analyzer.group_commits(commits);
@@ -219,19 +1638,19 @@ fn format_markdown(
         output.push_str(&format!("- Deletions: -{}\n", stats.total_deletions));
         output.push_str(&format!("- Bus factor: {:.1}\n", stats.bus_factor));
 
-        if let Some(first) = stats.first_commit {
-            if let Some(last) = stats.last_commit {
-                output.push_str(&format!(
-                    "- Period: {} to {} ({} days)\n",
-                    first.format("%Y-%m-%d"),
-                    last.format("%Y-%m-%d"),
-                    stats.period_days.unwrap_or(0)
-                ));
-                output.push_str(&format!(
-                    "- Commits per day: {:.2}\n",
-                    stats.commits_per_day
-                ));
-            }
+        if let Some(first) = stats.first_commit
+            && let Some(last) = stats.last_commit
+        {
+            output.push_str(&format!(
+                "- Period: {} to {} ({} days)\n",
+                first.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d"),
+                stats.period_days.unwrap_or(0)
+            ));
+            output.push_str(&format!(
+                "- Commits per day: {:.2}\n",
+                stats.commits_per_day
+            ));
         }
 
         output.push('\n');
@@ -255,11 +1674,11 @@ fn format_markdown(
     }
 
     // Footer
-    if let Some(config) = &analyzer.config {
-        if let Some(footer) = &config.templates.footer {
-            output.push_str(footer);
-            output.push('\n');
-        }
+    if let Some(config) = &analyzer.config
+        && let Some(footer) = &config.templates.footer
+    {
+        output.push_str(footer);
+        output.push('\n');
     }
 
     output
@@ -285,6 +1704,10 @@ fn format_markdown_commit(commit: &CommitInfo) -> String {
         line.push_str(&format!("{} ", emoji));
     }
 
+    if commit.breaking {
+        line.push_str("**BREAKING** ");
+    }
+
     line.push_str(&commit.summary);
 
     // Add hash and author
@@ -308,15 +1731,19 @@ fn format_markdown_commit(commit: &CommitInfo) -> String {
 
     line.push('\n');
 
+    for note in &commit.notes {
+        line.push_str(&format!("  > **Note:** {}\n", note));
+    }
+
     let mut output = String::new();
 
-    if let Some(body) = &commit.body {
-        if !body.trim().is_empty() {
-            for l in body.lines() {
-                let trimmed = l.trim();
-                if !trimmed.is_empty() {
-                    output.push_str(&format!("  > {}\n", trimmed));
-                }
+    if let Some(body) = &commit.body
+        && !body.trim().is_empty()
+    {
+        for l in body.lines() {
+            let trimmed = l.trim();
+            if !trimmed.is_empty() {
+                output.push_str(&format!("  > {}\n", trimmed));
             }
         }
     }
@@ -344,6 +1771,40 @@ fn format_json(
     serde_json::to_string_pretty(&output).map_err(Into::into)
 }
 
+fn format_csv(commits: &[CommitInfo], columns: &[config::CsvColumn]) -> String {
+    use config::CsvColumn;
+
+    let mut output = columns
+        .iter()
+        .map(|c| c.name())
+        .collect::<Vec<_>>()
+        .join(",");
+    output.push('\n');
+
+    for commit in commits {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                CsvColumn::Hash => commit.hash.clone(),
+                CsvColumn::Date => commit.date.to_rfc3339(),
+                CsvColumn::Author => utils::csv_field(&commit.author.name),
+                CsvColumn::Email => utils::csv_field(&commit.author.email),
+                CsvColumn::Type => utils::csv_field(commit.commit_type.as_deref().unwrap_or("")),
+                CsvColumn::Scope => utils::csv_field(commit.scope.as_deref().unwrap_or("")),
+                CsvColumn::Summary => utils::csv_field(&commit.summary),
+                CsvColumn::Insertions => commit.insertions.to_string(),
+                CsvColumn::Deletions => commit.deletions.to_string(),
+                CsvColumn::Files => commit.files_changed.len().to_string(),
+                CsvColumn::Tags => utils::csv_field(&commit.tags.join(";")),
+            })
+            .collect();
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+
+    output
+}
+
 fn format_text(
     analyzer: &GitAnalyzer,
     commits: &[CommitInfo],
@@ -405,16 +1866,16 @@ fn format_text(
         output.push_str(&format!("Deletions: -{}\n", stats.total_deletions));
         output.push_str(&format!("Bus factor: {:.1}\n", stats.bus_factor));
 
-        if let Some(first) = stats.first_commit {
-            if let Some(last) = stats.last_commit {
-                output.push_str(&format!(
-                    "Period: {} to {} ({} days)\n",
-                    first.format("%Y-%m-%d"),
-                    last.format("%Y-%m-%d"),
-                    stats.period_days.unwrap_or(0)
-                ));
-                output.push_str(&format!("Commits per day: {:.2}\n", stats.commits_per_day));
-            }
+        if let Some(first) = stats.first_commit
+            && let Some(last) = stats.last_commit
+        {
+            output.push_str(&format!(
+                "Period: {} to {} ({} days)\n",
+                first.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d"),
+                stats.period_days.unwrap_or(0)
+            ));
+            output.push_str(&format!("Commits per day: {:.2}\n", stats.commits_per_day));
         }
 
         if !stats.authors.is_empty() {
@@ -439,7 +1900,13 @@ fn format_text(
 fn format_text_commit(commit: &CommitInfo) -> String {
     let mut line = String::new();
 
-    line.push_str(&format!("* {}", commit.summary));
+    line.push_str("* ");
+
+    if commit.breaking {
+        line.push_str("[BREAKING] ");
+    }
+
+    line.push_str(&commit.summary);
 
     // Add hash and author
     line.push_str(&format!(" [{}]", commit.short_hash));
@@ -465,14 +1932,18 @@ fn format_text_commit(commit: &CommitInfo) -> String {
 
     line.push('\n');
 
+    for note in &commit.notes {
+        line.push_str(&format!("    Note: {}\n", note));
+    }
+
     // Add body if present
-    if let Some(body) = &commit.body {
-        if !body.trim().is_empty() {
-            for line_body in body.lines().take(3) {
-                // Limit body lines
-                if !line_body.trim().is_empty() {
-                    line.push_str(&format!("    > {}\n", line_body.trim()));
-                }
+    if let Some(body) = &commit.body
+        && !body.trim().is_empty()
+    {
+        for line_body in body.lines().take(3) {
+            // Limit body lines
+            if !line_body.trim().is_empty() {
+                line.push_str(&format!("    > {}\n", line_body.trim()));
             }
         }
     }
@@ -495,19 +1966,19 @@ fn format_stats_markdown(stats: &crate::core::RepositoryStats, detailed: bool) -
     ));
     output.push_str(&format!("- **Bus factor:** {:.1}\n", stats.bus_factor));
 
-    if let Some(first) = stats.first_commit {
-        if let Some(last) = stats.last_commit {
-            output.push_str(&format!(
-                "- **Period:** {} to {} ({} days)\n",
-                first.format("%Y-%m-%d"),
-                last.format("%Y-%m-%d"),
-                stats.period_days.unwrap_or(0)
-            ));
-            output.push_str(&format!(
-                "- **Commits per day:** {:.2}\n",
-                stats.commits_per_day
-            ));
-        }
+    if let Some(first) = stats.first_commit
+        && let Some(last) = stats.last_commit
+    {
+        output.push_str(&format!(
+            "- **Period:** {} to {} ({} days)\n",
+            first.format("%Y-%m-%d"),
+            last.format("%Y-%m-%d"),
+            stats.period_days.unwrap_or(0)
+        ));
+        output.push_str(&format!(
+            "- **Commits per day:** {:.2}\n",
+            stats.commits_per_day
+        ));
     }
 
     if let Some(day) = &stats.most_active_day {
@@ -596,6 +2067,33 @@ fn format_stats_markdown(stats: &crate::core::RepositoryStats, detailed: bool) -
     output
 }
 
+fn format_stats_csv(stats: &crate::core::RepositoryStats) -> String {
+    let mut output = String::from(
+        "author,email,commits,insertions,deletions,first_commit,last_commit,commit_types",
+    );
+    for author in &stats.authors {
+        let mut types: Vec<String> = author
+            .commit_types
+            .iter()
+            .map(|(k, v)| format!("{}:{}", k, v))
+            .collect();
+        types.sort();
+        let fields = [
+            utils::csv_field(&author.author.name),
+            utils::csv_field(&author.author.email),
+            author.commits.to_string(),
+            author.insertions.to_string(),
+            author.deletions.to_string(),
+            author.first_commit.to_rfc3339(),
+            author.last_commit.to_rfc3339(),
+            utils::csv_field(&types.join(";")),
+        ];
+        output.push('\n');
+        output.push_str(&fields.join(","));
+    }
+    output
+}
+
 fn format_stats_json(stats: &crate::core::RepositoryStats, detailed: bool) -> Result<String> {
     #[derive(serde::Serialize)]
     struct Output {
@@ -664,19 +2162,19 @@ fn format_stats_text(stats: &crate::core::RepositoryStats, detailed: bool) -> St
     ));
     output.push_str(&format!("Bus factor:         {:.1}\n", stats.bus_factor));
 
-    if let Some(first) = stats.first_commit {
-        if let Some(last) = stats.last_commit {
-            output.push_str(&format!(
-                "Period:             {} to {} ({} days)\n",
-                first.format("%Y-%m-%d"),
-                last.format("%Y-%m-%d"),
-                stats.period_days.unwrap_or(0)
-            ));
-            output.push_str(&format!(
-                "Commits per day:    {:.2}\n",
-                stats.commits_per_day
-            ));
-        }
+    if let Some(first) = stats.first_commit
+        && let Some(last) = stats.last_commit
+    {
+        output.push_str(&format!(
+            "Period:             {} to {} ({} days)\n",
+            first.format("%Y-%m-%d"),
+            last.format("%Y-%m-%d"),
+            stats.period_days.unwrap_or(0)
+        ));
+        output.push_str(&format!(
+            "Commits per day:    {:.2}\n",
+            stats.commits_per_day
+        ));
     }
 
     if let Some(day) = &stats.most_active_day {
@@ -688,7 +2186,7 @@ fn format_stats_text(stats: &crate::core::RepositoryStats, detailed: bool) -> St
         stats.most_active_hour
     ));
 
-    output.push_str("\n");
+    output.push('\n');
     output.push_str("TOP CONTRIBUTORS\n");
     output.push_str(&"-".repeat(80));
     output.push('\n');
@@ -709,7 +2207,7 @@ fn format_stats_text(stats: &crate::core::RepositoryStats, detailed: bool) -> St
     }
 
     if detailed {
-        output.push_str("\n");
+        output.push('\n');
         output.push_str("DETAILED STATISTICS\n");
         output.push_str(&"-".repeat(80));
         output.push('\n');
@@ -752,7 +2250,7 @@ fn format_stats_text(stats: &crate::core::RepositoryStats, detailed: bool) -> St
         }
 
         if !stats.commit_types.is_empty() {
-            output.push_str("\n");
+            output.push('\n');
             output.push_str("COMMIT TYPES\n");
             output.push_str(&"-".repeat(80));
             output.push('\n');
@@ -0,0 +1,239 @@
+use crate::config::Config;
+use crate::conventional;
+use crate::core::parse_trailers;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+const NOT_PAST_TENSE: &[&str] = &["need", "embed", "feed", "seed", "speed", "shed", "shred"];
+const NOT_GERUND: &[&str] = &["bring", "ping", "ring", "sing", "string", "swing", "thing"];
+const THIRD_PERSON: &[&str] = &[
+    "adds",
+    "bumps",
+    "changes",
+    "cleans",
+    "creates",
+    "deletes",
+    "fixes",
+    "implements",
+    "improves",
+    "makes",
+    "moves",
+    "removes",
+    "renames",
+    "refactors",
+    "updates",
+    "uses",
+];
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintResult {
+    pub commit: Option<String>,
+    pub summary: String,
+    pub violations: Vec<Violation>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintReport {
+    pub results: Vec<LintResult>,
+}
+impl LintReport {
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+    fn count(&self, severity: Severity) -> usize {
+        self.results
+            .iter()
+            .flat_map(|r| &r.violations)
+            .filter(|v| v.severity == severity)
+            .count()
+    }
+}
+pub struct Linter {
+    config: Config,
+    allowed_types: BTreeSet<String>,
+    allowed_scopes: BTreeSet<String>,
+}
+impl Linter {
+    pub fn new(config: Config) -> Self {
+        let mut allowed_types: BTreeSet<String> = config
+            .grouping
+            .groups
+            .iter()
+            .flat_map(|g| g.types())
+            .map(|t| config.conventional.normalize_type(t))
+            .collect();
+        if allowed_types.is_empty() {
+            allowed_types = conventional::STANDARD_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect();
+        }
+        let allowed_scopes = config
+            .grouping
+            .groups
+            .iter()
+            .flat_map(|g| &g.scopes)
+            .cloned()
+            .collect();
+        Self {
+            config,
+            allowed_types,
+            allowed_scopes,
+        }
+    }
+    pub fn lint(&self, commit: Option<String>, message: &str) -> LintResult {
+        let summary = message.lines().next().unwrap_or("").to_string();
+        let mut violations = Vec::new();
+        let mut error = |rule: &str, message: String| {
+            violations.push(Violation {
+                rule: rule.to_string(),
+                severity: Severity::Error,
+                message,
+            })
+        };
+        let lint = &self.config.lint;
+        let parsed = conventional::parse(message);
+        match &parsed {
+            Err(reason) => error("format", reason.clone()),
+            Ok(parsed) => {
+                let commit_type = self.config.conventional.normalize_type(&parsed.commit_type);
+                if !self.allowed_types.contains(&commit_type) {
+                    error(
+                        "type",
+                        format!(
+                            "type '{}' is not allowed (expected one of: {})",
+                            parsed.commit_type,
+                            self.allowed_types
+                                .iter()
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    );
+                }
+                if let Some(scope) = &parsed.scope
+                    && !self.allowed_scopes.is_empty()
+                    && !self.allowed_scopes.contains(scope)
+                {
+                    error(
+                        "scope",
+                        format!(
+                            "scope '{}' is not allowed (expected one of: {})",
+                            scope,
+                            self.allowed_scopes
+                                .iter()
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    );
+                }
+            }
+        }
+        let summary_length = summary.chars().count();
+        if lint.max_summary_length > 0 && summary_length > lint.max_summary_length {
+            error(
+                "summary-length",
+                format!(
+                    "summary is {} characters long (max {})",
+                    summary_length, lint.max_summary_length
+                ),
+            );
+        }
+        if lint.body_line_length > 0 {
+            for (i, line) in message.lines().enumerate().skip(1) {
+                let length = line.chars().count();
+                // Long URLs cannot be wrapped, so lines without spaces are exempt.
+                if length > lint.body_line_length && line.trim().contains(' ') {
+                    error(
+                        "body-wrap",
+                        format!(
+                            "line {} is {} characters long (max {})",
+                            i + 1,
+                            length,
+                            lint.body_line_length
+                        ),
+                    );
+                }
+            }
+        }
+        let trailers = parse_trailers(message);
+        for required in &lint.required_trailers {
+            if !trailers
+                .iter()
+                .any(|t| t.key.eq_ignore_ascii_case(required))
+            {
+                error(
+                    "required-trailer",
+                    format!("missing required trailer '{}'", required),
+                );
+            }
+        }
+        if lint.imperative_mood {
+            let description = match &parsed {
+                Ok(parsed) => parsed.description.as_str(),
+                Err(_) => summary.as_str(),
+            };
+            if let Some(word) = description.split_whitespace().next()
+                && !is_imperative(word)
+            {
+                violations.push(Violation {
+                    rule: "imperative-mood".to_string(),
+                    severity: Severity::Warning,
+                    message: format!(
+                        "summary should use the imperative mood ('{}' does not look imperative)",
+                        word
+                    ),
+                });
+            }
+        }
+        LintResult {
+            commit,
+            summary,
+            violations,
+        }
+    }
+}
+pub fn clean_message(raw: &str) -> String {
+    let mut lines = Vec::new();
+    for line in raw.lines() {
+        // Everything below git's scissors line is the verbose diff.
+        if line.starts_with("# ") && line.contains(">8") {
+            break;
+        }
+        if !line.starts_with('#') {
+            lines.push(line.trim_end());
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+fn is_imperative(word: &str) -> bool {
+    let word = word
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    if word.len() <= 3 {
+        return true;
+    }
+    if word.ends_with("ed") && !NOT_PAST_TENSE.contains(&word.as_str()) {
+        return false;
+    }
+    if word.ends_with("ing") && !NOT_GERUND.contains(&word.as_str()) {
+        return false;
+    }
+    !THIRD_PERSON.contains(&word.as_str())
+}
@@ -1,6 +1,9 @@
 use clap::Parser;
-use rcgen::cli::{Cli, Commands};
+use rcgen::GenOptions;
+use rcgen::cli::{Cli, Commands, HooksAction, OutputFormat, StatsFormat};
 use rcgen::error::Result;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
@@ -11,6 +14,7 @@ fn main() -> Result<()> {
             limit,
             author,
             grep,
+            filter,
             since,
             until,
             body,
@@ -20,23 +24,44 @@ fn main() -> Result<()> {
             stats,
             release,
             diff_stats,
+            formatter,
+            theme,
         } => {
-            let changelog = rcgen::generate_changelog(
-                &path,
+            let options = GenOptions {
                 format,
                 limit,
-                author.as_deref(),
-                grep.as_deref(),
-                since.as_deref(),
-                until.as_deref(),
-                body,
-                group,
-                sort,
-                no_merges,
-                stats,
-                diff_stats,
-                release,
-            )?;
+                author,
+                grep,
+                filter,
+                since,
+                until,
+                include_body: body,
+                group_by_type: group,
+                sort_order: sort,
+                exclude_merges: no_merges,
+                include_stats: stats,
+                include_diff_stats: diff_stats,
+                release_mode: release,
+                formatter,
+                theme,
+            };
+            if options.format == OutputFormat::Ndjson && options.formatter.is_none() {
+                // Commits are written as they are read instead of being
+                // collected first.
+                let mut writer: Box<dyn Write> = match &output {
+                    Some(output_path) => Box::new(BufWriter::new(File::create(output_path)?)),
+                    None => Box::new(std::io::stdout().lock()),
+                };
+                let warnings = rcgen::write_ndjson(&path, &mut writer, &options)?;
+                writer.flush()?;
+                print_warnings(&warnings);
+                if let Some(output_path) = output {
+                    println!("Changelog written to {}", output_path);
+                }
+                return Ok(());
+            }
+            let (changelog, warnings) = rcgen::generate_changelog(&path, &options)?;
+            print_warnings(&warnings);
             if let Some(output_path) = output {
                 std::fs::write(&output_path, changelog)?;
                 println!("Changelog written to {}", output_path);
@@ -48,16 +73,89 @@ fn main() -> Result<()> {
             path,
             detailed,
             format,
+            theme,
         } => {
-            let stats = rcgen::generate_stats(&path, detailed, format)?;
+            if format == StatsFormat::Ndjson {
+                let mut stdout = std::io::stdout().lock();
+                let warnings = rcgen::write_stats_ndjson(&path, &mut stdout)?;
+                stdout.flush()?;
+                print_warnings(&warnings);
+                return Ok(());
+            }
+            let (stats, warnings) = rcgen::generate_stats(&path, detailed, format, theme)?;
+            print_warnings(&warnings);
             println!("{}", stats);
         }
         Commands::Init { path, force } => {
             rcgen::init_config(&path, force)?;
             println!("Configuration initialized at {}/.rcgen.toml", path);
         }
+        Commands::Lint {
+            path,
+            range,
+            message_file,
+            format,
+        } => {
+            let report = rcgen::lint_commits(&path, range.as_deref(), message_file.as_deref())?;
+            println!("{}", rcgen::format_lint_report(&report, format)?);
+            if report.has_errors() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Hooks { action } => match action {
+            HooksAction::Install {
+                path,
+                post_commit,
+                on_tag,
+            } => {
+                for message in rcgen::install_hooks(&path, post_commit, on_tag)? {
+                    println!("{}", message);
+                }
+            }
+            HooksAction::Uninstall { path } => {
+                let messages = rcgen::uninstall_hooks(&path)?;
+                if messages.is_empty() {
+                    println!("No rcgen hooks installed");
+                }
+                for message in messages {
+                    println!("{}", message);
+                }
+            }
+            HooksAction::Run { path, hook, args } => {
+                let outcome = rcgen::run_hook(&path, &hook, &args)?;
+                if !outcome.output.is_empty() {
+                    eprintln!("{}", outcome.output);
+                }
+                if !outcome.success {
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Release {
+            path,
+            dry_run,
+            bump,
+            version,
+        } => {
+            let summary = rcgen::release(&path, dry_run, bump, version.as_deref())?;
+            println!("{}", summary);
+        }
+        Commands::WhichRelease {
+            path,
+            query,
+            format,
+        } => {
+            println!("{}", rcgen::which_release(&path, &query, format)?);
+        }
+        Commands::Tags { path, format } => {
+            println!("{}", rcgen::release_timeline(&path, format)?);
+        }
+        Commands::Export { path, sqlite } => {
+            println!("{}", rcgen::export_sqlite(&path, &sqlite)?);
+        }
         Commands::Preview { path, limit } => {
-            let preview = rcgen::preview_changelog(&path, limit)?;
+            let (preview, warnings) = rcgen::preview_changelog(&path, limit)?;
+            print_warnings(&warnings);
             println!("{}", preview);
         }
         Commands::Diff { .. } => {
@@ -66,3 +164,8 @@ fn main() -> Result<()> {
     }
     Ok(())
 }
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+}
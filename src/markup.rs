@@ -0,0 +1,274 @@
+use crate::core::{CommitInfo, GitAnalyzer};
+use crate::release::{self, ReleaseSection};
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Markup {
+    AsciiDoc,
+    ReStructuredText,
+}
+// Characters that start inline formatting, attribute references or macros in
+// AsciiDoc. `<`, `>` and `&` are escaped by the processor itself.
+const ASCIIDOC_SPECIAL: &[char] = &[
+    '*', '_', '`', '#', '^', '~', '+', '[', ']', '{', '}', '\\', '|',
+];
+const RST_SPECIAL: &[char] = &['\\', '*', '`', '_', '|'];
+// The output has no document title, so it can be included into an existing
+// page: releases are second-level sections and groups third-level ones.
+pub fn render(
+    markup: Markup,
+    analyzer: &GitAnalyzer,
+    sections: &[ReleaseSection],
+    base_url: Option<&str>,
+    date_format: &str,
+) -> String {
+    let mut blocks = Vec::new();
+    for section in sections {
+        if section.tag.is_none() && section.commits.is_empty() {
+            continue;
+        }
+        let title = match section.date {
+            Some(date) => format!("{} - {}", section.label, date.format(date_format)),
+            None => section.label.clone(),
+        };
+        let mut block = heading(markup, 1, &title, Some(&release::anchor(&section.label)));
+
+        if let (Some(base), Some(previous)) = (base_url, &section.previous) {
+            let target = section.tag.as_deref().unwrap_or("HEAD");
+            block.push_str(&format!(
+                "\n{}\n",
+                link(
+                    markup,
+                    &release::compare_link(base, previous, target),
+                    &format!("Compare {}...{}", previous, target)
+                )
+            ));
+        }
+
+        for group in analyzer.group_commits(&section.commits) {
+            block.push('\n');
+            block.push_str(&heading(markup, 2, &group.name, None));
+            if let Some(description) = &group.description {
+                block.push_str(&format!("\n{}\n", inline(markup, description)));
+            }
+            block.push('\n');
+            for commit in &group.commits {
+                block.push_str(&item(markup, commit, base_url));
+            }
+        }
+        if !section.new_contributors.is_empty() {
+            block.push('\n');
+            block.push_str(&heading(markup, 2, "New Contributors", None));
+            block.push('\n');
+            for contributor in &section.new_contributors {
+                let commit = match &contributor.url {
+                    Some(url) => link(markup, url, &contributor.short_hash),
+                    None => code(markup, &contributor.short_hash),
+                };
+                block.push_str(&format!(
+                    "* {} made their first contribution in {}\n",
+                    inline(markup, &contributor.author.name),
+                    commit
+                ));
+            }
+        }
+        blocks.push(block);
+    }
+    if blocks.is_empty() {
+        return "No commits found\n".to_string();
+    }
+    blocks.join("\n")
+}
+fn heading(markup: Markup, level: usize, title: &str, anchor: Option<&str>) -> String {
+    let mut output = String::new();
+    match markup {
+        Markup::AsciiDoc => {
+            if let Some(anchor) = anchor {
+                output.push_str(&format!("[[{}]]\n", anchor));
+            }
+            output.push_str(&format!(
+                "{} {}\n",
+                "=".repeat(level + 1),
+                inline(markup, title)
+            ));
+        }
+        Markup::ReStructuredText => {
+            if let Some(anchor) = anchor {
+                output.push_str(&format!(".. _{}:\n\n", anchor));
+            }
+            let title = inline(markup, title);
+            let adornment = if level == 1 { '=' } else { '-' };
+            output.push_str(&format!(
+                "{}\n{}\n",
+                title,
+                adornment.to_string().repeat(title.chars().count())
+            ));
+        }
+    }
+    output
+}
+fn item(markup: Markup, commit: &CommitInfo, base_url: Option<&str>) -> String {
+    let mut text = String::new();
+    if commit.breaking {
+        text.push_str(match markup {
+            Markup::AsciiDoc => "*BREAKING:* ",
+            Markup::ReStructuredText => "**BREAKING:** ",
+        });
+    }
+    text.push_str(&inline(markup, &commit.summary));
+
+    let hash = match base_url {
+        Some(base) => link(
+            markup,
+            &release::commit_link(base, &commit.hash),
+            &commit.short_hash,
+        ),
+        None => code(markup, &commit.short_hash),
+    };
+    text.push_str(&format!(
+        " ({}, {})",
+        hash,
+        inline(markup, &commit.author.name)
+    ));
+
+    let mut output = format!("* {}\n", text);
+    for note in &commit.notes {
+        output.push_str(&format!("\n  {}\n", inline(markup, note)));
+    }
+    output
+}
+fn link(markup: Markup, url: &str, text: &str) -> String {
+    match markup {
+        Markup::AsciiDoc => format!("{}[{}]", url, text.replace(']', "\\]")),
+        // Anonymous references avoid duplicate target names across releases.
+        Markup::ReStructuredText => format!(
+            "`{} <{}>`__",
+            text.replace('\\', "\\\\")
+                .replace('<', "\\<")
+                .replace('`', "\\`"),
+            url
+        ),
+    }
+}
+fn code(markup: Markup, text: &str) -> String {
+    match markup {
+        // A `+` would end the literal passthrough early.
+        Markup::AsciiDoc if text.contains('+') => {
+            format!("`pass:c[{}]`", text.replace(']', "\\]"))
+        }
+        Markup::AsciiDoc => format!("``+{}+``", text),
+        Markup::ReStructuredText => format!("``{}``", text),
+    }
+}
+// Escapes text for the target markup while keeping `code spans` as inline
+// literals, since commit messages routinely use them.
+fn inline(markup: Markup, text: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let Some(length) = rest[start + 1..].find('`') else {
+            break;
+        };
+        let before = &rest[..start];
+        let span = &rest[start + 1..start + 1 + length];
+        let after = &rest[start + length + 2..];
+        output.push_str(&escape(markup, before));
+        if span.is_empty() {
+            output.push_str(&escape(markup, "``"));
+        } else {
+            // reST only recognizes inline markup at word boundaries; an
+            // escaped space marks one without adding any text.
+            let joined = |c: Option<char>| {
+                markup == Markup::ReStructuredText && c.is_some_and(char::is_alphanumeric)
+            };
+            if joined(before.chars().next_back()) {
+                output.push_str("\\ ");
+            }
+            output.push_str(&code(markup, span));
+            if joined(after.chars().next()) {
+                output.push_str("\\ ");
+            }
+        }
+        rest = after;
+    }
+    output.push_str(&escape(markup, rest));
+    output
+}
+fn escape(markup: Markup, text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    match markup {
+        // Backslashes only escape some AsciiDoc markup, so text with special
+        // characters goes through a passthrough that only escapes HTML.
+        Markup::AsciiDoc if text.contains(ASCIIDOC_SPECIAL) => {
+            format!("pass:c[{}]", text.replace(']', "\\]"))
+        }
+        Markup::AsciiDoc => text.to_string(),
+        Markup::ReStructuredText => {
+            let mut escaped = String::with_capacity(text.len());
+            for c in text.chars() {
+                if RST_SPECIAL.contains(&c) {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn asciidoc_passthrough_escapes_brackets() {
+        assert_eq!(escape(Markup::AsciiDoc, "plain text"), "plain text");
+        assert_eq!(
+            escape(Markup::AsciiDoc, "fix [a] and *b*"),
+            "pass:c[fix [a\\] and *b*]"
+        );
+        assert_eq!(
+            inline(Markup::AsciiDoc, "use `x[0]` here"),
+            "use ``+x[0]+`` here"
+        );
+    }
+    #[test]
+    fn asciidoc_code_with_plus() {
+        assert_eq!(code(Markup::AsciiDoc, "a1b2c3d4"), "``+a1b2c3d4+``");
+        assert_eq!(code(Markup::AsciiDoc, "c++"), "`pass:c[c++]`");
+        assert_eq!(code(Markup::AsciiDoc, "a+b[i]"), "`pass:c[a+b[i\\]]`");
+        assert_eq!(
+            inline(Markup::AsciiDoc, "support `x += 1` syntax"),
+            "support `pass:c[x += 1]` syntax"
+        );
+    }
+    #[test]
+    fn rst_code_spans_next_to_word_characters() {
+        assert_eq!(
+            inline(Markup::ReStructuredText, "use `foo` now"),
+            "use ``foo`` now"
+        );
+        assert_eq!(
+            inline(Markup::ReStructuredText, "the `foo`s and re`bar`"),
+            "the ``foo``\\ s and re\\ ``bar``"
+        );
+        assert_eq!(inline(Markup::ReStructuredText, "(`foo`)"), "(``foo``)");
+        assert_eq!(
+            inline(Markup::ReStructuredText, "a *b* `c_d`"),
+            "a \\*b\\* ``c_d``"
+        );
+    }
+    #[test]
+    fn rst_link_text_is_escaped() {
+        assert_eq!(
+            link(
+                Markup::ReStructuredText,
+                "https://example.com",
+                "a <b> `c` d\\e"
+            ),
+            "`a \\<b> \\`c\\` d\\\\e <https://example.com>`__"
+        );
+        assert_eq!(
+            link(Markup::AsciiDoc, "https://example.com", "x[1]"),
+            "https://example.com[x[1\\]]"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::conventional;
+use crate::core::{CommitInfo, GitAnalyzer};
+use crate::release::ReleaseSection;
+use crate::utils;
+use crate::version::{Identifier, Version};
+pub fn render(analyzer: &GitAnalyzer, sections: &[ReleaseSection], width: usize) -> String {
+    let mut blocks = Vec::new();
+    for section in sections {
+        if section.tag.is_none() && section.commits.is_empty() {
+            continue;
+        }
+        let mut block = format!("{}\n", heading(section));
+        for group in analyzer.group_commits(&section.commits) {
+            block.push_str(&format!("\n** {}\n", group.name));
+            for commit in &group.commits {
+                block.push('\n');
+                block.push_str(&item(commit, width));
+            }
+        }
+        blocks.push(block);
+    }
+    if blocks.is_empty() {
+        return "No commits found\n".to_string();
+    }
+    blocks.join("\n\n")
+}
+// Unreleased changes use the placeholder heading GNU projects keep at the top
+// of NEWS between releases.
+fn heading(section: &ReleaseSection) -> String {
+    let (Some(_), Some(date)) = (&section.tag, section.date) else {
+        return "* Noteworthy changes in release ?.? (????-??-??) [?]".to_string();
+    };
+    let stability = match Version::parse(&section.label) {
+        Some(version) if version.is_prerelease() => {
+            let alpha = version
+                .pre
+                .iter()
+                .any(|p| matches!(p, Identifier::AlphaNumeric(s) if s.to_lowercase().starts_with("alpha")));
+            if alpha { "alpha" } else { "beta" }
+        }
+        _ => "stable",
+    };
+    format!(
+        "* Noteworthy changes in release {} ({}) [{}]",
+        section.label,
+        date.format("%Y-%m-%d"),
+        stability
+    )
+}
+fn item(commit: &CommitInfo, width: usize) -> String {
+    let mut text = utils::capitalize(conventional::description(&commit.summary));
+    if let Some(scope) = &commit.scope {
+        text = format!("{}: {}", scope, text);
+    }
+    if commit.breaking {
+        text = format!("BREAKING: {}", text);
+    }
+    let mut output = paragraph(&text, "  - ", "    ", width);
+    for note in &commit.notes {
+        output.push('\n');
+        output.push_str(&paragraph(note, "    ", "    ", width));
+    }
+    output
+}
+fn paragraph(text: &str, first: &str, rest: &str, width: usize) -> String {
+    let mut output = String::new();
+    let lines = utils::wrap_text(text, width.saturating_sub(first.len()).max(1));
+    for (index, line) in lines.iter().enumerate() {
+        output.push_str(if index == 0 { first } else { rest });
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn section(label: &str, tag: Option<&str>) -> ReleaseSection {
+        ReleaseSection {
+            label: label.to_string(),
+            tag: tag.map(str::to_string),
+            previous: None,
+            date: tag.map(|_| "2024-02-15T10:00:00+00:00".parse().unwrap()),
+            commits: Vec::new(),
+            new_contributors: Vec::new(),
+        }
+    }
+    fn stability(label: &str) -> String {
+        let heading = heading(&section(label, Some(&format!("v{}", label))));
+        heading[heading.rfind('[').unwrap()..].to_string()
+    }
+    #[test]
+    fn heading_stability_labels() {
+        assert_eq!(
+            heading(&section("1.2.0", Some("v1.2.0"))),
+            "* Noteworthy changes in release 1.2.0 (2024-02-15) [stable]"
+        );
+        assert_eq!(stability("1.2.0-alpha"), "[alpha]");
+        assert_eq!(stability("1.2.0-Alpha.2"), "[alpha]");
+        assert_eq!(stability("1.2.0-beta.1"), "[beta]");
+        assert_eq!(stability("1.2.0-rc.1"), "[beta]");
+        // Labels that are not semantic versions are taken as stable releases.
+        assert_eq!(stability("2024.02"), "[stable]");
+    }
+    #[test]
+    fn unreleased_heading_is_a_placeholder() {
+        assert_eq!(
+            heading(&section("Unreleased", None)),
+            "* Noteworthy changes in release ?.? (????-??-??) [?]"
+        );
+    }
+    #[test]
+    fn paragraphs_hang_under_the_bullet() {
+        assert_eq!(
+            paragraph(
+                "Rotate the TLS certificates used by the API",
+                "  - ",
+                "    ",
+                20
+            ),
+            "  - Rotate the TLS\n    certificates\n    used by the API\n"
+        );
+        // The text keeps at least one column even when the indent fills the width.
+        assert_eq!(paragraph("a b", "  - ", "    ", 2), "  - a\n    b\n");
+    }
+}
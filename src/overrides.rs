@@ -0,0 +1,230 @@
+use crate::config::ConventionalConfig;
+use crate::core::CommitInfo;
+use crate::error::{RcgenError, Result};
+use git2::{ErrorCode, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+pub const OVERRIDES_FILE: &str = ".rcgen-overrides.toml";
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CommitOverride {
+    #[serde(default)]
+    pub skip: bool,
+    pub rewrite_summary: Option<String>,
+    pub set_type: Option<String>,
+    pub set_scope: Option<String>,
+    pub add_note: Option<String>,
+    #[serde(default)]
+    pub mark_breaking: bool,
+}
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    entries: HashMap<String, CommitOverride>,
+    warnings: Vec<String>,
+}
+impl Overrides {
+    pub fn load(
+        path: &str,
+        repo: &Repository,
+        conventional: &ConventionalConfig,
+    ) -> Result<Option<Self>> {
+        let overrides_path = Path::new(path).join(OVERRIDES_FILE);
+        if !overrides_path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(overrides_path)?;
+        let raw: BTreeMap<String, CommitOverride> = toml::from_str(&content)
+            .map_err(|e| RcgenError::Config(format!("{}: {}", OVERRIDES_FILE, e)))?;
+        let mut entries = HashMap::new();
+        let mut keys: HashMap<String, String> = HashMap::new();
+        let mut warnings = Vec::new();
+        for (key, mut entry) in raw {
+            // Keys are full or abbreviated commit hashes; refs and revision
+            // expressions such as `HEAD~3` would change meaning over time.
+            if !(4..=40).contains(&key.len()) || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+                warnings.push(format!(
+                    "{}: '{}' is not a commit hash or hash prefix",
+                    OVERRIDES_FILE, key
+                ));
+                continue;
+            }
+            // Keys may be abbreviated; resolve them once so lookups are exact.
+            let id = match repo.find_commit_by_prefix(&key) {
+                Ok(commit) => commit.id().to_string(),
+                Err(e) if e.code() == ErrorCode::Ambiguous => {
+                    warnings.push(format!(
+                        "{}: '{}' matches more than one commit; use a longer prefix",
+                        OVERRIDES_FILE, key
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    warnings.push(format!(
+                        "{}: '{}' does not match a commit: {}",
+                        OVERRIDES_FILE,
+                        key,
+                        e.message()
+                    ));
+                    continue;
+                }
+            };
+            if let Some(first) = keys.get(&id) {
+                warnings.push(format!(
+                    "{}: '{}' and '{}' refer to the same commit; ignoring '{}'",
+                    OVERRIDES_FILE, first, key, key
+                ));
+                continue;
+            }
+            // Match the normalization applied to header, trailer and note types.
+            entry.set_type = entry
+                .set_type
+                .map(|commit_type| conventional.normalize_type(&commit_type));
+            keys.insert(id.clone(), key);
+            entries.insert(id, entry);
+        }
+        Ok(Some(Self { entries, warnings }))
+    }
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+    pub fn get(&self, hash: &str) -> Option<&CommitOverride> {
+        self.entries.get(hash)
+    }
+    pub fn apply(&self, mut commit: CommitInfo) -> Option<CommitInfo> {
+        let Some(entry) = self.get(&commit.hash) else {
+            return Some(commit);
+        };
+        if entry.skip {
+            return None;
+        }
+        if let Some(summary) = &entry.rewrite_summary {
+            commit.summary = summary.clone();
+        }
+        if let Some(commit_type) = &entry.set_type {
+            commit.commit_type = Some(commit_type.clone());
+            commit.explicit_type = true;
+        }
+        if let Some(scope) = &entry.set_scope {
+            commit.scope = Some(scope.clone());
+        }
+        if let Some(note) = &entry.add_note {
+            commit.notes.push(note.clone());
+        }
+        if entry.mark_breaking {
+            commit.breaking = true;
+        }
+        Some(commit)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempRepo;
+    use serde_json::json;
+    use std::collections::HashSet;
+    fn commit(hash: &str) -> CommitInfo {
+        serde_json::from_value(json!({
+            "hash": hash,
+            "short_hash": &hash[..8],
+            "author": {"name": "Alice", "email": "alice@example.com", "commits_count": 1},
+            "date": "2024-02-15T10:00:00+00:00",
+            "message": "add users endpoint",
+            "summary": "add users endpoint",
+            "body": null,
+            "files_changed": [],
+            "insertions": 0,
+            "deletions": 0,
+            "is_merge": false,
+            "tags": [],
+            "branches": [],
+            "commit_type": null,
+            "scope": null
+        }))
+        .unwrap()
+    }
+    fn load(repo: &TempRepo, content: &str) -> Overrides {
+        repo.write(OVERRIDES_FILE, content);
+        Overrides::load(repo.path(), &repo.repo, &ConventionalConfig::default())
+            .unwrap()
+            .unwrap()
+    }
+    #[test]
+    fn resolves_hash_prefixes() {
+        let mut repo = TempRepo::new();
+        let id = repo.commit("add users endpoint").to_string();
+        let overrides = load(&repo, &format!("[{}]\nset_scope = \"api\"\n", &id[..7]));
+        assert!(overrides.warnings().is_empty());
+        assert_eq!(
+            overrides.get(&id).unwrap().set_scope.as_deref(),
+            Some("api")
+        );
+    }
+    #[test]
+    fn applies_skip_type_and_scope() {
+        let mut repo = TempRepo::new();
+        let skipped = repo.commit("wip").to_string();
+        let kept = repo.commit("add users endpoint").to_string();
+        let overrides = load(
+            &repo,
+            &format!(
+                "[{}]\nskip = true\n\n[{}]\nset_type = \"Feature\"\nset_scope = \"api\"\n",
+                &skipped[..8],
+                kept
+            ),
+        );
+        assert!(overrides.apply(commit(&skipped)).is_none());
+        let applied = overrides.apply(commit(&kept)).unwrap();
+        // set_type goes through the same aliases as commit headers.
+        assert_eq!(applied.commit_type.as_deref(), Some("feat"));
+        assert!(applied.explicit_type);
+        assert_eq!(applied.scope.as_deref(), Some("api"));
+        let untouched = "0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(overrides.apply(commit(untouched)).unwrap().hash, untouched);
+    }
+    #[test]
+    fn warns_about_invalid_and_duplicate_keys() {
+        let mut repo = TempRepo::new();
+        let id = repo.commit("add users endpoint").to_string();
+        let overrides = load(
+            &repo,
+            &format!(
+                "[{}]\nskip = true\n\n[{}]\nset_scope = \"api\"\n\n[HEAD]\nskip = true\n\n[ffff0000]\nskip = true\n",
+                &id[..6],
+                id
+            ),
+        );
+        let warnings = overrides.warnings();
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("'HEAD' is not a commit hash"))
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("refer to the same commit"))
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("'ffff0000' does not match"))
+        );
+        // The first key in file order wins.
+        assert!(overrides.get(&id).unwrap().skip);
+    }
+    #[test]
+    fn warns_about_ambiguous_prefixes() {
+        let mut repo = TempRepo::new();
+        // Commit hashes are deterministic here, so this always finds the same pair.
+        let mut seen = HashSet::new();
+        let prefix = (0..)
+            .map(|i| repo.commit(&format!("commit {}", i)).to_string()[..4].to_string())
+            .find(|prefix| !seen.insert(prefix.clone()))
+            .unwrap();
+        let overrides = load(&repo, &format!("[{}]\nskip = true\n", prefix));
+        assert_eq!(overrides.warnings().len(), 1);
+        assert!(overrides.warnings()[0].contains("matches more than one commit"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::version::Version;
+use chrono::{DateTime, FixedOffset};
+#[derive(Debug, Clone)]
+pub struct PackageRelease {
+    pub version: String,
+    // `version` without the packaging revision.
+    pub upstream: String,
+    pub date: DateTime<FixedOffset>,
+    pub changes: Vec<String>,
+}
+pub fn package_name(name: &str) -> String {
+    let name: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    name.trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string()
+}
+pub fn upstream_version(label: &str) -> Option<String> {
+    let upstream: String = match Version::parse(label) {
+        Some(version) => {
+            let mut upstream = format!("{}.{}.{}", version.major, version.minor, version.patch);
+            // `~` sorts before anything in both dpkg and rpm, so 1.0.0~rc.1
+            // comes before 1.0.0 the way semver orders them.
+            if version.is_prerelease() {
+                let pre: Vec<String> = version.pre.iter().map(|p| p.to_string()).collect();
+                upstream.push_str(&format!("~{}", pre.join(".")));
+            }
+            if let Some(build) = &version.build {
+                upstream.push_str(&format!("+{}", build));
+            }
+            upstream
+        }
+        None => label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '~') {
+                    c
+                } else {
+                    '.'
+                }
+            })
+            .collect(),
+    };
+    if !upstream.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(upstream)
+}
+pub fn package_version(upstream: &str, revision: &str) -> String {
+    if revision.is_empty() {
+        upstream.to_string()
+    } else {
+        format!("{}-{}", upstream, revision)
+    }
+}
+pub fn debian(
+    package: &str,
+    distribution: &str,
+    urgency: &str,
+    maintainer: &str,
+    releases: &[PackageRelease],
+) -> String {
+    let mut entries = Vec::new();
+    for release in releases {
+        let mut entry = format!(
+            "{} ({}) {}; urgency={}\n\n",
+            package, release.version, distribution, urgency
+        );
+        if release.changes.is_empty() {
+            entry.push_str(&format!("  * New upstream release {}.\n", release.upstream));
+        }
+        for change in &release.changes {
+            entry.push_str(&format!("  * {}\n", change));
+        }
+        entry.push_str(&format!(
+            "\n -- {}  {}\n",
+            maintainer,
+            release.date.format("%a, %d %b %Y %H:%M:%S %z")
+        ));
+        entries.push(entry);
+    }
+    entries.join("\n")
+}
+pub fn rpm(maintainer: &str, releases: &[PackageRelease]) -> String {
+    let mut entries = Vec::new();
+    for release in releases {
+        let mut entry = format!(
+            "* {} {} - {}\n",
+            release.date.format("%a %b %d %Y"),
+            maintainer,
+            release.version
+        );
+        if release.changes.is_empty() {
+            entry.push_str(&format!("- New upstream release {}\n", release.upstream));
+        }
+        for change in &release.changes {
+            // rpmbuild expands macros inside %changelog.
+            entry.push_str(&format!("- {}\n", change.replace('%', "%%")));
+        }
+        entries.push(entry);
+    }
+    entries.join("\n")
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn release(upstream: &str, revision: &str) -> PackageRelease {
+        PackageRelease {
+            version: package_version(upstream, revision),
+            upstream: upstream.to_string(),
+            date: "2024-02-15T10:00:00+00:00".parse().unwrap(),
+            changes: Vec::new(),
+        }
+    }
+    #[test]
+    fn versions() {
+        assert_eq!(
+            upstream_version("1.2.0-rc.1").as_deref(),
+            Some("1.2.0~rc.1")
+        );
+        assert_eq!(upstream_version("2024.02").as_deref(), Some("2024.02"));
+        assert_eq!(upstream_version("next"), None);
+        assert_eq!(package_version("1.2.0", "1"), "1.2.0-1");
+        assert_eq!(package_version("1.2.0", ""), "1.2.0");
+    }
+    #[test]
+    fn empty_releases_name_the_upstream_version() {
+        let releases = [release("1.2.0", "2")];
+        let debian = debian("widget", "unstable", "medium", "A <a@b>", &releases);
+        assert!(debian.starts_with("widget (1.2.0-2) unstable; urgency=medium\n"));
+        assert!(debian.contains("  * New upstream release 1.2.0.\n"));
+        let rpm = rpm("A <a@b>", &releases);
+        assert!(rpm.starts_with("* Thu Feb 15 2024 A <a@b> - 1.2.0-2\n"));
+        assert!(rpm.contains("- New upstream release 1.2.0\n"));
+    }
+}
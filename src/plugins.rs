@@ -0,0 +1,216 @@
+use crate::config::PluginCommand;
+use crate::core::{CommitGroup, CommitInfo, RepositoryStats};
+use crate::error::{RcgenError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum FilterResponse {
+    Keep,
+    Drop,
+    Modify { commit: Box<CommitInfo> },
+}
+#[derive(Debug, Serialize)]
+pub struct ChangelogDocument<'a> {
+    pub commits: &'a [CommitInfo],
+    pub groups: Option<Vec<CommitGroup>>,
+    pub stats: Option<&'a RepositoryStats>,
+    pub release_mode: bool,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+pub fn run_filter(
+    plugin: &PluginCommand,
+    cwd: Option<&Path>,
+    commit: &CommitInfo,
+) -> Result<Option<CommitInfo>> {
+    let input = serde_json::to_vec(commit)?;
+    let output = run_plugin(plugin, cwd, &input)?;
+    // An empty response is treated as "keep" so trivial filters can stay silent.
+    if output.trim().is_empty() {
+        return Ok(Some(commit.clone()));
+    }
+    let response: FilterResponse = serde_json::from_str(&output).map_err(|e| {
+        RcgenError::Plugin(format!("{}: invalid filter response: {}", plugin.name, e))
+    })?;
+    Ok(match response {
+        FilterResponse::Keep => Some(commit.clone()),
+        FilterResponse::Drop => None,
+        FilterResponse::Modify { commit } => Some(*commit),
+    })
+}
+pub fn run_formatter(
+    plugin: &PluginCommand,
+    cwd: Option<&Path>,
+    document: &ChangelogDocument,
+) -> Result<String> {
+    let input = serde_json::to_vec(document)?;
+    run_plugin(plugin, cwd, &input)
+}
+fn run_plugin(plugin: &PluginCommand, cwd: Option<&Path>, input: &[u8]) -> Result<String> {
+    let mut command = Command::new(&plugin.command);
+    command
+        .args(&plugin.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(dir) = cwd {
+        command.current_dir(dir);
+    }
+    let mut child = command.spawn().map_err(|e| {
+        RcgenError::Plugin(format!(
+            "{}: failed to spawn '{}': {}",
+            plugin.name, plugin.command, e
+        ))
+    })?;
+    // Pipes are drained on separate threads so a chatty plugin cannot deadlock
+    // against us while we wait for it to exit.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_vec();
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).map(|_| buf)
+    });
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = String::new();
+        stderr.read_to_string(&mut buf).map(|_| buf)
+    });
+    // A timeout too large to represent is as good as none.
+    let deadline = match plugin.timeout_secs {
+        0 => None,
+        secs => Instant::now().checked_add(Duration::from_secs(secs)),
+    };
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // The pipes close with the child, so the threads finish promptly.
+            let _ = writer.join();
+            let _ = stdout_reader.join();
+            let _ = stderr_reader.join();
+            return Err(RcgenError::PluginTimeout(
+                plugin.name.clone(),
+                plugin.timeout_secs,
+            ));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    // A plugin may exit without reading its input; a broken pipe is not an error.
+    let _ = writer.join();
+    let stdout = stdout_reader
+        .join()
+        .map_err(|_| RcgenError::Plugin(format!("{}: stdout reader panicked", plugin.name)))??;
+    let stderr = stderr_reader
+        .join()
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or_default();
+    if !status.success() {
+        return Err(RcgenError::Plugin(format!(
+            "{}: exited with {}: {}",
+            plugin.name,
+            status,
+            stderr.trim()
+        )));
+    }
+    String::from_utf8(stdout)
+        .map_err(|e| RcgenError::Plugin(format!("{}: output is not UTF-8: {}", plugin.name, e)))
+}
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    fn plugin(script: &str, timeout_secs: u64) -> PluginCommand {
+        PluginCommand {
+            name: "test".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            timeout_secs,
+        }
+    }
+    fn commit() -> CommitInfo {
+        serde_json::from_value(json!({
+            "hash": "0123456789abcdef0123456789abcdef01234567",
+            "short_hash": "01234567",
+            "author": {"name": "Alice", "email": "alice@example.com", "commits_count": 1},
+            "date": "2024-02-15T10:00:00+00:00",
+            "message": "feat: add users endpoint",
+            "summary": "feat: add users endpoint",
+            "body": null,
+            "files_changed": [],
+            "insertions": 0,
+            "deletions": 0,
+            "is_merge": false,
+            "tags": [],
+            "branches": [],
+            "commit_type": "feat",
+            "scope": null
+        }))
+        .unwrap()
+    }
+    #[test]
+    fn filter_responses() {
+        let kept = run_filter(&plugin("cat >/dev/null", 10), None, &commit()).unwrap();
+        assert_eq!(kept.unwrap().hash, commit().hash);
+        let dropped = run_filter(
+            &plugin("cat >/dev/null; echo '{\"action\":\"drop\"}'", 10),
+            None,
+            &commit(),
+        )
+        .unwrap();
+        assert!(dropped.is_none());
+    }
+    #[test]
+    fn times_out_and_kills_the_plugin() {
+        let started = Instant::now();
+        let slow = PluginCommand {
+            command: "sleep".to_string(),
+            args: vec!["30".to_string()],
+            ..plugin("", 1)
+        };
+        match run_plugin(&slow, None, b"{}") {
+            Err(RcgenError::PluginTimeout(name, 1)) => assert_eq!(name, "test"),
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+    #[test]
+    fn reports_non_zero_exit_with_stderr() {
+        match run_plugin(&plugin("echo boom >&2; exit 3", 10), None, b"{}") {
+            Err(RcgenError::Plugin(message)) => {
+                assert!(message.contains("exit status: 3"), "{}", message);
+                assert!(message.ends_with("boom"), "{}", message);
+            }
+            other => panic!("expected a plugin error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn rejects_invalid_json() {
+        match run_filter(&plugin("echo 'not json'", 10), None, &commit()) {
+            Err(RcgenError::Plugin(message)) => {
+                assert!(message.contains("invalid filter response"), "{}", message)
+            }
+            other => panic!("expected a plugin error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn reports_spawn_failures() {
+        let missing = PluginCommand {
+            command: "rcgen-no-such-plugin".to_string(),
+            ..plugin("", 10)
+        };
+        match run_plugin(&missing, None, b"{}") {
+            Err(RcgenError::Plugin(message)) => assert!(message.contains("failed to spawn")),
+            other => panic!("expected a plugin error, got {:?}", other),
+        }
+    }
+}
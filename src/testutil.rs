@@ -0,0 +1,89 @@
+use git2::{Oid, Repository, Signature, Time};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// A throwaway repository under the system temp directory, removed on drop.
+pub struct TempRepo {
+    pub dir: PathBuf,
+    pub repo: Repository,
+    time: i64,
+}
+
+impl TempRepo {
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "rcgen-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let repo = Repository::init(&dir).unwrap();
+        Self {
+            dir,
+            repo,
+            time: 1_700_000_000,
+        }
+    }
+    pub fn path(&self) -> &str {
+        self.dir.to_str().unwrap()
+    }
+    pub fn write(&self, path: &str, content: &str) {
+        let full = self.dir.join(path);
+        if let Some(parent) = full.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(full, content).unwrap();
+    }
+    pub fn commit(&mut self, message: &str) -> Oid {
+        self.commit_as("Alice", "alice@example.com", message)
+    }
+    pub fn commit_as(&mut self, name: &str, email: &str, message: &str) -> Oid {
+        self.time += 3600;
+        let signature = Signature::new(name, email, &Time::new(self.time, 0)).unwrap();
+        let mut index = self.repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        self.repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+    }
+    // An annotated tag on HEAD.
+    pub fn tag(&self, name: &str, message: &str) {
+        let head = self
+            .repo
+            .head()
+            .unwrap()
+            .peel(git2::ObjectType::Commit)
+            .unwrap();
+        let signature =
+            Signature::new("Alice", "alice@example.com", &Time::new(self.time, 0)).unwrap();
+        self.repo
+            .tag(name, &head, &signature, message, false)
+            .unwrap();
+    }
+}
+
+impl Drop for TempRepo {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
@@ -54,15 +54,81 @@ fn parse_relative_date(date_str: &str) -> Option<DateTime<FixedOffset>> {
     DateTime::from_timestamp(timestamp, 0)
         .map(|dt| dt.with_timezone(&FixedOffset::east_opt(0).unwrap()))
 }
+pub fn git_time(time: git2::Time) -> Result<DateTime<FixedOffset>> {
+    let offset = FixedOffset::east_opt(time.offset_minutes() * 60)
+        .ok_or_else(|| RcgenError::DateParse("Invalid offset".to_string()))?;
+    Ok(DateTime::from_timestamp(time.seconds(), 0)
+        .ok_or_else(|| RcgenError::DateParse("Invalid timestamp".to_string()))?
+        .with_timezone(&offset))
+}
 pub fn format_date(date: &DateTime<FixedOffset>, format: &str) -> String {
     date.format(format).to_string()
 }
-pub fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        return s.to_string();
+// Splits text into lines of at most `width` characters at whitespace. Words
+// longer than the width, such as URLs, are kept whole on a line of their own.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut length = 0;
+    for word in text.split_whitespace() {
+        let word_length = word.chars().count();
+        if length > 0 && length + 1 + word_length > width {
+            lines.push(std::mem::take(&mut line));
+            length = 0;
+        }
+        if length > 0 {
+            line.push(' ');
+            length += 1;
+        }
+        line.push_str(word);
+        length += word_length;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+pub fn matches_tag_pattern(pattern: &str, tag: &str) -> bool {
+    // Anchored patterns are regexes; anything else is a git-style glob such as
+    // the default `v[0-9]*`.
+    if pattern.starts_with('^') || pattern.ends_with('$') {
+        return Regex::new(pattern).is_ok_and(|re| re.is_match(tag));
+    }
+    let mut regex = String::from("^");
+    let mut in_class = false;
+    for c in pattern.chars() {
+        match c {
+            '[' if !in_class => {
+                in_class = true;
+                regex.push('[');
+            }
+            ']' if in_class => {
+                in_class = false;
+                regex.push(']');
+            }
+            '!' if in_class && regex.ends_with('[') => regex.push('^'),
+            _ if in_class => regex.push(c),
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).is_ok_and(|re| re.is_match(tag))
+}
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
-    let truncated = &s[..max_len - 3];
-    format!("{}...", truncated)
 }
 pub fn get_file_extension(path: &str) -> Option<String> {
     std::path::Path::new(path)
@@ -71,7 +137,7 @@ pub fn get_file_extension(path: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 pub fn is_binary_file(content: &[u8]) -> bool {
-    content.iter().any(|&b| b == 0)
+    content.contains(&0)
 }
 pub fn human_readable_size(bytes: u64) -> String {
     const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
@@ -85,3 +151,33 @@ pub fn human_readable_size(bytes: u64) -> String {
     let size = bytes_f64 / base.powi(exp);
     format!("{:.1} {}", size, unit)
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn wrap_text_counts_characters_not_bytes() {
+        assert_eq!(
+            wrap_text("Änderungen für größere Dateien", 12),
+            vec!["Änderungen", "für größere", "Dateien"]
+        );
+        assert_eq!(
+            wrap_text("日本語 テキスト を 折り返す", 7),
+            vec!["日本語", "テキスト を", "折り返す"]
+        );
+    }
+    #[test]
+    fn wrap_text_keeps_long_words_whole() {
+        let url = "https://example.com/a/very/long/path/to/somewhere";
+        assert_eq!(
+            wrap_text(&format!("see {} for details", url), 20),
+            vec!["see", url, "for details"]
+        );
+    }
+    #[test]
+    fn wrap_text_handles_tiny_widths() {
+        assert_eq!(wrap_text("a bc d", 0), vec!["a", "bc", "d"]);
+        assert_eq!(wrap_text("a bc d", 1), vec!["a", "bc", "d"]);
+        assert_eq!(wrap_text("a b", 3), vec!["a b"]);
+        assert!(wrap_text("  \n ", 10).is_empty());
+    }
+}
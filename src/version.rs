@@ -0,0 +1,235 @@
+use crate::cli::Bump;
+use std::cmp::Ordering;
+use std::fmt;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Option<String>,
+}
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: None,
+        }
+    }
+    pub fn parse(input: &str) -> Option<Self> {
+        let (rest, build) = match input.split_once('+') {
+            Some((rest, build)) if !build.is_empty() => (rest, Some(build.to_string())),
+            Some(_) => return None,
+            None => (input, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre)),
+            Some(_) => return None,
+            None => (rest, None),
+        };
+        let mut numbers = core.split('.').map(parse_number);
+        let version = Self {
+            major: numbers.next()??,
+            minor: numbers.next()??,
+            patch: numbers.next()??,
+            pre: match pre {
+                Some(pre) => pre
+                    .split('.')
+                    .map(|part| {
+                        if part.is_empty() {
+                            None
+                        } else if part.chars().all(|c| c.is_ascii_digit()) {
+                            part.parse().ok().map(Identifier::Numeric)
+                        } else {
+                            Some(Identifier::AlphaNumeric(part.to_string()))
+                        }
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+                None => Vec::new(),
+            },
+            build,
+        };
+        if numbers.next().is_some() {
+            return None;
+        }
+        Some(version)
+    }
+    pub fn from_tag(tag: &str, prefix: Option<&str>) -> Option<(String, Self)> {
+        let start = match prefix {
+            Some(prefix) => {
+                tag.strip_prefix(prefix)?;
+                prefix.len()
+            }
+            // Without a configured prefix everything before the first digit is
+            // treated as one, e.g. `v` or `release-`.
+            None => tag.find(|c: char| c.is_ascii_digit())?,
+        };
+        let version = Self::parse(&tag[start..])?;
+        Some((tag[..start].to_string(), version))
+    }
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+    pub fn same_release(&self, other: &Self) -> bool {
+        (self.major, self.minor, self.patch) == (other.major, other.minor, other.patch)
+    }
+    pub fn bump(&self, bump: Bump) -> Self {
+        match bump {
+            // Releasing a pre-release finalizes it when it already carries the
+            // requested bump, e.g. 2.0.0-rc.1 becomes 2.0.0 rather than 3.0.0.
+            Bump::Major if self.is_prerelease() && self.minor == 0 && self.patch == 0 => {
+                Self::new(self.major, 0, 0)
+            }
+            Bump::Major => Self::new(self.major + 1, 0, 0),
+            Bump::Minor if self.is_prerelease() && self.patch == 0 => {
+                Self::new(self.major, self.minor, 0)
+            }
+            Bump::Minor => Self::new(self.major, self.minor + 1, 0),
+            Bump::Patch if self.is_prerelease() => Self::new(self.major, self.minor, self.patch),
+            Bump::Patch => Self::new(self.major, self.minor, self.patch + 1),
+        }
+    }
+}
+fn parse_number(part: &str) -> Option<u64> {
+    if part.is_empty() || (part.len() > 1 && part.starts_with('0')) {
+        return None;
+    }
+    part.parse().ok()
+}
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{}", n),
+            Identifier::AlphaNumeric(s) => write!(f, "{}", s),
+        }
+    }
+}
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            let pre: Vec<String> = self.pre.iter().map(|p| p.to_string()).collect();
+            write!(f, "-{}", pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::AlphaNumeric(_)) => Ordering::Less,
+            (Identifier::AlphaNumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::AlphaNumeric(a), Identifier::AlphaNumeric(b)) => a.cmp(b),
+        }
+    }
+}
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A pre-release has lower precedence than the release itself.
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+// Build metadata does not take part in precedence, so equality ignores it too.
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Version {}
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn v(input: &str) -> Version {
+        Version::parse(input).unwrap()
+    }
+    #[test]
+    fn pre_releases_sort_before_the_release() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+    #[test]
+    fn rejects_invalid_versions() {
+        for input in [
+            "1.0",
+            "1.0.0.0",
+            "01.0.0",
+            "1.0.0-",
+            "1.0.0+",
+            "1.0.0-a..b",
+            "v1.0.0",
+        ] {
+            assert!(Version::parse(input).is_none(), "accepted {}", input);
+        }
+    }
+    #[test]
+    fn build_metadata_is_ignored_for_precedence_and_equality() {
+        assert_eq!(v("1.0.0+build.1"), v("1.0.0+build.2"));
+        assert_eq!(v("1.0.0+build.1").cmp(&v("1.0.0")), Ordering::Equal);
+        assert_ne!(v("1.0.0-rc.1+a"), v("1.0.0+a"));
+    }
+    #[test]
+    fn bumps_reset_lower_components() {
+        assert_eq!(v("1.2.3").bump(Bump::Major).to_string(), "2.0.0");
+        assert_eq!(v("1.2.3").bump(Bump::Minor).to_string(), "1.3.0");
+        assert_eq!(v("1.2.3+build").bump(Bump::Patch).to_string(), "1.2.4");
+    }
+    #[test]
+    fn bumps_finalize_matching_pre_releases() {
+        assert_eq!(v("2.0.0-rc.1").bump(Bump::Major).to_string(), "2.0.0");
+        assert_eq!(v("2.1.0-rc.1").bump(Bump::Minor).to_string(), "2.1.0");
+        assert_eq!(v("2.1.3-rc.1").bump(Bump::Patch).to_string(), "2.1.3");
+        assert_eq!(v("2.1.0-rc.1").bump(Bump::Major).to_string(), "3.0.0");
+        assert_eq!(v("2.1.3-rc.1").bump(Bump::Minor).to_string(), "2.2.0");
+    }
+    #[test]
+    fn tags_split_into_prefix_and_version() {
+        let (prefix, version) = Version::from_tag("release-1.2.3", None).unwrap();
+        assert_eq!(prefix, "release-");
+        assert_eq!(version, Version::new(1, 2, 3));
+        assert!(Version::from_tag("v1.2.3", Some("rel-")).is_none());
+        assert!(Version::from_tag("latest", None).is_none());
+    }
+}